@@ -0,0 +1,18 @@
+#!/bin/sh
+# Build the web app into a self-contained dist/, installable and playable offline.
+# Requires trunk and the wasm32-unknown-unknown target:
+#   rustup target add wasm32-unknown-unknown && cargo install trunk
+set -e
+cd "$(dirname "$0")"
+
+trunk build --release --dist dist
+
+# list every file for the service worker to cache, and name the cache after their hash
+cd dist
+FILES=$(find . -type f ! -name sw.js | sed 's|^\.||' | sort)
+LIST=$(echo "$FILES" | sed 's|.*|"&"|' | paste -sd, -)
+VERSION=$(echo "$FILES" | sed 's|^|.|' | xargs cat | cksum | cut -d' ' -f1)
+sed "s|__VERSION__|$VERSION|; s|\"__FILES__\"|$LIST|" sw.js > sw.js.tmp
+mv sw.js.tmp sw.js
+
+echo "dist/ is ready, serve it as static files with every unknown path falling back to index.html"
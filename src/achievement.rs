@@ -0,0 +1,78 @@
+use {
+	crate::{plate::*, statistic::Statistic},
+	serde::{Deserialize, Serialize},
+	std::collections::HashSet,
+};
+
+/// Milestone unlocked once and kept in the state file
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Achievement {
+	/// win with the first guess
+	FirstGuess,
+	/// win 10 games in a row
+	Streak10,
+	/// win in difficult mode with the last guess
+	HardLastGuess,
+	/// win without guessing a letter already known to be absent
+	NoGreyReuse,
+	/// play 100 games
+	Games100,
+}
+
+impl Achievement {
+	pub const ALL: [Achievement; 5] = [
+		Achievement::FirstGuess,
+		Achievement::Streak10,
+		Achievement::HardLastGuess,
+		Achievement::NoGreyReuse,
+		Achievement::Games100,
+	];
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			Achievement::FirstGuess => "Hole in One",
+			Achievement::Streak10 => "On Fire",
+			Achievement::HardLastGuess => "Close Call",
+			Achievement::NoGreyReuse => "Clean Sweep",
+			Achievement::Games100 => "Centurion",
+		}
+	}
+
+	pub fn description(&self) -> &'static str {
+		match self {
+			Achievement::FirstGuess => "win with the first guess",
+			Achievement::Streak10 => "win 10 games in a row",
+			Achievement::HardLastGuess => "win in difficult mode with all 6 guesses",
+			Achievement::NoGreyReuse => "win without reusing a grey letter",
+			Achievement::Games100 => "play 100 games",
+		}
+	}
+
+	/// whether the last game `plate`, already added to `statistic`, meets this milestone
+	pub fn is_met(&self, plate: &Plate, statistic: &Statistic) -> bool {
+		match self {
+			Achievement::FirstGuess => plate.is_win() && plate.count() == 1,
+			Achievement::Streak10 => statistic.current_streak() >= 10,
+			Achievement::HardLastGuess => {
+				plate.is_win()
+					&& plate.count() == MAX_GUESSES
+					&& plate.difficulty() != Difficulty::Normal
+			}
+			Achievement::NoGreyReuse => plate.is_win() && !reuses_grey(plate),
+			Achievement::Games100 => statistic.games().len() >= 100,
+		}
+	}
+}
+
+/// whether a guess contains a letter found absent by an earlier guess
+fn reuses_grey(plate: &Plate) -> bool {
+	let mut grey: HashSet<Letter> = HashSet::new();
+	for (word, _) in plate.history() {
+		if word.iter().any(|c| grey.contains(c)) {
+			return true;
+		}
+		grey.extend(word.iter().filter(|c| !plate.goal().contains(c)));
+	}
+	return false;
+}
@@ -0,0 +1,178 @@
+use {
+	crate::{
+		knowledge::Knowledge,
+		pattern::{expected_size, matrix::PatternMatrix, pattern_counts},
+		plate::*,
+	},
+	serde::{Deserialize, Serialize},
+	std::collections::HashSet,
+};
+
+/// Quality of a single guess
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct GuessAnalysis {
+	pub guess:         String,
+	/// candidates remaining before the guess
+	pub before:        usize,
+	/// candidates remaining after the guess
+	pub after:         usize,
+	/// expected candidates remaining after the guess
+	pub expected:      f64,
+	/// acceptable word with the least expected remaining candidates
+	pub best_guess:    String,
+	pub best_expected: f64,
+	/// 0 ~ 100, information gained compared to the best guess
+	pub skill:         f64,
+	/// bits of information gained beyond expectation
+	pub luck:          f64,
+}
+
+/// Post-mortem of a whole game
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Analysis {
+	pub guesses: Vec<GuessAnalysis>,
+	/// average skill of all guesses
+	pub skill:   f64,
+	/// total luck of all guesses
+	pub luck:    f64,
+}
+
+/// expected number of `candidates` remaining after guessing `guess`
+pub fn expected_remaining(guess: &Word, candidates: &[Word]) -> f64 {
+	expected_size(&pattern_counts(guess, candidates))
+}
+
+/// `n` acceptable words with the least expected remaining candidates, best first
+/// ties are broken by preferring candidates, then alphabetical order
+/// patterns are looked up in `matrix` if it has every word, or computed
+pub fn ranked_guesses(
+	candidates: &[Word],
+	set_acceptable: &HashSet<Word>,
+	matrix: Option<&PatternMatrix>,
+	n: usize,
+) -> Vec<(Word, f64)> {
+	let set_candidates: HashSet<&Word> = candidates.iter().collect();
+	let mut ranked: Vec<(Word, f64)> = matrix
+		.and_then(|m| m.expected_remaining(set_acceptable, candidates))
+		.unwrap_or_else(|| {
+			set_acceptable
+				.iter()
+				.map(|w| (*w, expected_remaining(w, candidates)))
+				.collect()
+		});
+	ranked.sort_by(|(w0, e0), (w1, e1)| {
+		e0.total_cmp(e1)
+			.then(
+				set_candidates
+					.contains(w1)
+					.cmp(&set_candidates.contains(w0)),
+			)
+			.then(w0.cmp(w1))
+	});
+	ranked.truncate(n);
+	return ranked;
+}
+
+/// acceptable word with the least expected remaining candidates, see `ranked_guesses`
+pub fn best_guess(
+	candidates: &[Word],
+	set_acceptable: &HashSet<Word>,
+	matrix: Option<&PatternMatrix>,
+) -> (Word, f64) {
+	return ranked_guesses(candidates, set_acceptable, matrix, 1)
+		.pop()
+		.unwrap_or((candidates[0], 1.0));
+}
+
+/// bits of information when `n` candidates shrink to `remaining`
+/// none once no candidate is left, e.g. for an answer out of the final list
+fn bits(n: usize, remaining: f64) -> f64 {
+	(n.max(1) as f64 / remaining.max(1.0)).log2()
+}
+
+/// analyze every guess of `plate` against candidates in `list_final`
+pub fn analyze(
+	plate: &Plate,
+	list_final: &[Word],
+	set_acceptable: &HashSet<Word>,
+	matrix: Option<&PatternMatrix>,
+) -> Analysis {
+	let mut knowledge = Knowledge::new();
+	let mut candidates: Vec<Word> = list_final.to_vec();
+	let mut guesses = vec![];
+
+	for (word, state) in plate.history() {
+		let before = candidates.len();
+		let expected = expected_remaining(word, &candidates);
+		let (best_guess, best_expected) = match before {
+			0 | 1 => (*word, expected),
+			_ => best_guess(&candidates, set_acceptable, matrix),
+		};
+
+		knowledge.add(word, state);
+		candidates.retain(|w| knowledge.matches(w));
+		let after = candidates.len();
+
+		let best_bits = bits(before, best_expected);
+		guesses.push(GuessAnalysis {
+			guess: word_to_str(word),
+			before,
+			after,
+			expected,
+			best_guess: word_to_str(&best_guess),
+			best_expected,
+			skill: match best_bits > 0.0 {
+				true => (bits(before, expected) / best_bits * 100.0).min(100.0),
+				false => 100.0,
+			},
+			luck: bits(before, after as f64) - bits(before, expected),
+		});
+	}
+
+	return Analysis {
+		skill: guesses.iter().map(|g| g.skill).sum::<f64>() / guesses.len().max(1) as f64,
+		luck: guesses.iter().map(|g| g.luck).sum(),
+		guesses,
+	};
+}
+
+/// let the solver play `answer`, always taking the best guess allowed by `difficulty`
+pub fn solve(
+	answer: &Word,
+	list_final: &[Word],
+	set_acceptable: &HashSet<Word>,
+	matrix: Option<&PatternMatrix>,
+	difficulty: Difficulty,
+) -> Result<Plate, GuessError> {
+	if !set_acceptable.contains(answer) {
+		return Err(GuessError::NotAcceptable(*answer));
+	}
+	let mut plate = Plate::new(answer, difficulty);
+	let mut candidates: Vec<Word> = list_final.to_vec();
+	while !plate.is_over() {
+		let knowledge = plate.knowledge();
+		candidates.retain(|w| knowledge.matches(w));
+		// the answer is not in the final list
+		if candidates.is_empty() {
+			candidates = set_acceptable
+				.iter()
+				.filter(|w| knowledge.matches(w))
+				.copied()
+				.collect();
+			candidates.sort();
+		}
+		let guess = match candidates.len() {
+			1 | 2 => candidates[0],
+			_ => {
+				let allowed: HashSet<Word> = set_acceptable
+					.iter()
+					.filter(|w| knowledge.check(w, difficulty).is_ok())
+					.copied()
+					.collect();
+				best_guess(&candidates, &allowed, matrix).0
+			}
+		};
+		plate.guess(&guess)?;
+	}
+	return Ok(plate);
+}
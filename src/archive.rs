@@ -0,0 +1,62 @@
+use {
+	crate::{plate::*, statistic::Statistic, word_gen::rand_words},
+	std::time::{SystemTime, UNIX_EPOCH},
+};
+
+/// 2024-01-01 in days since the Unix epoch, the first daily puzzle
+pub const DAY_ONE: u64 = 19723;
+
+/// daily puzzle at `unix_secs`, counted from 1 on DAY_ONE
+pub fn day_at(unix_secs: u64) -> u32 {
+	return ((unix_secs / 86400).saturating_sub(DAY_ONE) + 1) as u32;
+}
+
+/// daily puzzle of today by the system clock
+pub fn today() -> u32 {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default();
+	return day_at(now.as_secs());
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DayStatus {
+	Unplayed,
+	Played,
+	Won,
+}
+
+/// A past daily puzzle
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ArchiveDay {
+	pub day:    u32,
+	pub answer: Word,
+	pub status: DayStatus,
+}
+
+/// daily puzzles 1 ~ `last` of `seed`, played or won if `statistic` has a game of its answer
+pub fn archive(
+	list_final: &Vec<Word>,
+	seed: u64,
+	last: u32,
+	statistic: &Statistic,
+) -> Vec<ArchiveDay> {
+	let mut next_word = rand_words(list_final, seed, 1);
+	return (1..=last)
+		.map_while(|day| {
+			let answer = next_word().ok()?;
+			let answer_str = word_to_str(&answer);
+			let mut games = statistic.games().iter().filter(|g| g.answer == answer_str);
+			let status = match games.clone().any(|g| g.is_win()) {
+				true => DayStatus::Won,
+				false if games.next().is_some() => DayStatus::Played,
+				false => DayStatus::Unplayed,
+			};
+			Some(ArchiveDay {
+				day,
+				answer,
+				status,
+			})
+		})
+		.collect();
+}
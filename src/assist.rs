@@ -0,0 +1,186 @@
+use {
+	crate::{
+		analysis::ranked_guesses, knowledge::Knowledge, pattern::matrix::PatternMatrix, plate::*,
+	},
+	std::{collections::HashSet, fmt},
+	LetterState::*,
+};
+
+/// suggestions printed after each step
+pub const SUGGESTIONS: usize = 5;
+
+/// A line typed in assist mode
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum AssistInput {
+	/// a guess and the feedback it received, e.g. `crane GYXXG`
+	Step(Word, WordState),
+	/// take back the last step
+	Undo,
+}
+
+/// Reasons for rejecting a line in assist mode
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum AssistError {
+	/// not `<guess> <feedback>` or `undo`
+	Format(String),
+	Word(WordError),
+	/// (letter, position): not a letter code of `LetterState::to_char`
+	Feedback(char, usize),
+	/// number of feedback letters is not 5
+	FeedbackLength(usize),
+	/// no acceptable word is consistent with the feedback, likely mistyped
+	NoCandidates,
+	/// nothing to undo
+	Empty,
+}
+
+impl fmt::Display for AssistError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			AssistError::Format(input) => {
+				write!(
+					f,
+					"expected `<guess> <feedback>` or `undo`, found {:?}",
+					input
+				)
+			}
+			AssistError::Word(e) => write!(f, "{}", e),
+			AssistError::Feedback(c, pos) => {
+				write!(
+					f,
+					"invalid feedback {:?} at position {}, use G, Y, or X or R for grey",
+					c,
+					pos + 1
+				)
+			}
+			AssistError::FeedbackLength(len) => {
+				write!(f, "expected 5 feedback letters, found {}", len)
+			}
+			AssistError::NoCandidates => write!(f, "no word matches the feedback"),
+			AssistError::Empty => write!(f, "nothing to undo"),
+		}
+	}
+}
+
+impl std::error::Error for AssistError {}
+
+impl std::str::FromStr for AssistInput {
+	type Err = AssistError;
+
+	/// `<guess> <feedback>` in letter codes of `Cmd`, case insensitive
+	/// a grey letter may be either R or X
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parts: Vec<&str> = s.split_whitespace().collect();
+		match parts[..] {
+			[undo] if undo.eq_ignore_ascii_case("undo") => return Ok(AssistInput::Undo),
+			[_, _] => {}
+			_ => return Err(AssistError::Format(s.trim().to_string())),
+		}
+		let word = word_from_str(parts[0]).map_err(AssistError::Word)?;
+		let codes: Vec<char> = parts[1].chars().collect();
+		if codes.len() != 5 {
+			return Err(AssistError::FeedbackLength(codes.len()));
+		}
+		let mut state = [Redundant; 5];
+		for (i, &c) in codes.iter().enumerate() {
+			state[i] = match LetterState::from_char(c) {
+				None => return Err(AssistError::Feedback(c, i)),
+				// other games have no unknown letters in a feedback
+				Some(Unknown) => Redundant,
+				Some(s) => s,
+			};
+		}
+		return Ok(AssistInput::Step(word, state));
+	}
+}
+
+/// Candidates of a puzzle played elsewhere, narrowed down by the feedback entered so far
+pub struct Assistant<'a> {
+	list_final:     &'a [Word],
+	set_acceptable: &'a HashSet<Word>,
+	/// patterns of both lists, computed when None
+	matrix:         Option<&'a PatternMatrix>,
+	history:        Vec<(Word, WordState)>,
+	candidates:     Vec<Word>,
+}
+
+impl<'a> Assistant<'a> {
+	pub fn new(
+		list_final: &'a [Word],
+		set_acceptable: &'a HashSet<Word>,
+		matrix: Option<&'a PatternMatrix>,
+	) -> Self {
+		Self {
+			list_final,
+			set_acceptable,
+			matrix,
+			history: vec![],
+			candidates: list_final.to_vec(),
+		}
+	}
+
+	/// words of the final list consistent with `history`
+	/// or acceptable ones if the answer is not in the final list
+	fn candidates_of(&self, history: &[(Word, WordState)]) -> Vec<Word> {
+		let knowledge = Knowledge::from_history(history);
+		let mut result: Vec<Word> = self
+			.list_final
+			.iter()
+			.filter(|w| knowledge.matches(w))
+			.copied()
+			.collect();
+		if result.is_empty() {
+			result = self
+				.set_acceptable
+				.iter()
+				.filter(|w| knowledge.matches(w))
+				.copied()
+				.collect();
+			result.sort();
+		}
+		return result;
+	}
+
+	/// apply a line of input, the assistant is unchanged on error
+	pub fn apply(&mut self, input: AssistInput) -> Result<(), AssistError> {
+		match input {
+			AssistInput::Step(word, state) => {
+				self.history.push((word, state));
+				let candidates = self.candidates_of(&self.history);
+				if candidates.is_empty() {
+					self.history.pop();
+					return Err(AssistError::NoCandidates);
+				}
+				self.candidates = candidates;
+			}
+			AssistInput::Undo => {
+				self.history.pop().ok_or(AssistError::Empty)?;
+				self.candidates = self.candidates_of(&self.history);
+			}
+		}
+		return Ok(());
+	}
+
+	pub fn history(&self) -> &[(Word, WordState)] {
+		&self.history
+	}
+	/// possible answers, ordered as the final list
+	pub fn candidates(&self) -> &[Word] {
+		&self.candidates
+	}
+	/// whether the last feedback is all green
+	pub fn is_solved(&self) -> bool {
+		self.history
+			.last()
+			.is_some_and(|(_, state)| state.iter().all(|s| *s == Correct))
+	}
+
+	/// best `n` next guesses with their expected remaining candidates
+	/// the only candidate left is the only suggestion
+	pub fn suggestions(&self, n: usize) -> Vec<(Word, f64)> {
+		match self.candidates.len() {
+			1 => vec![(self.candidates[0], 1.0)],
+			_ => ranked_guesses(&self.candidates, self.set_acceptable, self.matrix, n),
+		}
+	}
+}
@@ -0,0 +1,86 @@
+use {
+	anyhow::{anyhow, Result},
+	clap::Parser,
+	std::{net::TcpListener, path::PathBuf},
+	tiny_http::{Header, Server},
+	wordle::{config::load_lists, race, server::GameServer},
+};
+
+/// Wordle game server with a JSON REST API
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+	/// address to listen on, use port 0 to pick a free port
+	#[arg(short, long, default_value = "127.0.0.1:8000")]
+	listen: String,
+
+	/// directory of per-user state files and games, which are reloaded on restart
+	#[arg(short = 'S', long = "state-dir", value_name = "STATE_DIR")]
+	state_dir: Option<String>,
+
+	/// final word set
+	#[arg(short = 'f', long = "final-set", value_name = "FINAL_SET_FILE")]
+	final_set_src: Option<String>,
+
+	/// acceptable word set
+	#[arg(
+		short = 'a',
+		long = "acceptable-set",
+		value_name = "ACCEPTABLE_SET_FILE"
+	)]
+	acceptable_set_src: Option<String>,
+
+	/// also serve WebSocket races on this address, use port 0 to pick a free port
+	#[arg(long, value_name = "ADDR")]
+	race: Option<String>,
+
+	/// default number of racers to start a race room
+	#[arg(long, default_value_t = 2)]
+	race_players: usize,
+}
+
+fn main() -> Result<()> {
+	let args = Args::parse();
+	let (set_acceptable, list_final) = load_lists(args.acceptable_set_src, args.final_set_src)?;
+	if let Some(dir) = &args.state_dir {
+		std::fs::create_dir_all(dir)?;
+	}
+	let mut game_server = GameServer::new(
+		set_acceptable,
+		list_final,
+		args.state_dir.map(PathBuf::from),
+	)?;
+
+	let server = Server::http(&args.listen)
+		.map_err(|e| anyhow!("cannot listen on {}: {}", args.listen, e))?;
+	println!("listening on http://{}", server.server_addr());
+
+	if let Some(addr) = &args.race {
+		let listener =
+			TcpListener::bind(addr).map_err(|e| anyhow!("cannot listen on {}: {}", addr, e))?;
+		println!("racing on ws://{}", listener.local_addr()?);
+		let race_players = args.race_players;
+		std::thread::spawn(move || race::net::serve(listener, race_players));
+	}
+
+	for mut request in server.incoming_requests() {
+		let mut body = String::new();
+		let response = match request.as_reader().read_to_string(&mut body) {
+			Ok(_) => game_server.handle(request.method().as_str(), request.url(), &body),
+			Err(e) => wordle::server::Response {
+				status: 400,
+				body:   serde_json::json!({ "error": "bad_request", "message": e.to_string() })
+					.to_string(),
+			},
+		};
+		let response = tiny_http::Response::from_string(response.body)
+			.with_status_code(response.status)
+			.with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+			.with_header(Header::from_bytes("Access-Control-Allow-Origin", "*").unwrap());
+		if let Err(e) = request.respond(response) {
+			eprintln!("failed to respond: {}", e);
+		}
+	}
+
+	return Ok(());
+}
@@ -1,144 +1,748 @@
 use {
 	anyhow::{anyhow, Result},
-	std::{cell::Cell, collections::HashSet, rc::Rc},
-	web_sys::{wasm_bindgen::JsValue, window, FormData, HtmlFormElement},
+	std::{
+		cell::RefCell,
+		collections::{BTreeMap, HashSet},
+		rc::Rc,
+		time::Duration,
+	},
+	web_sys::{
+		js_sys::{encode_uri_component, Date},
+		wasm_bindgen::{closure::Closure, JsCast, JsValue},
+		window, FileReader, FormData, HtmlFormElement, HtmlInputElement, MessageEvent,
+		UrlSearchParams, WebSocket,
+	},
 	wordle::{
-		builtin_words, config::*, plate::*, statistic::Statistic, util::LetterMap,
+		achievement::Achievement,
+		analysis::{analyze, Analysis},
+		archive::*,
+		builtin_words,
+		challenge::Challenge,
+		config::*,
+		knowledge::Knowledge,
+		leaderboard::Leaderboard,
+		plate::*,
+		race::*,
+		statistic::Statistic,
+		util::LetterMap,
 		word_gen::rand_words,
 	},
 	yew::prelude::*,
 };
 
-fn alert(msg: &str) {
-	(|| -> Option<()> {
-		window()?.alert_with_message(msg).ok()?;
-		None
+/// run `f` once after `ms` milliseconds
+fn set_timeout(ms: i32, f: impl FnOnce() + 'static) {
+	if let Some(w) = window() {
+		let f = Closure::once_into_js(f);
+		let _ = w.set_timeout_with_callback_and_timeout_and_arguments_0(f.unchecked_ref(), ms);
+	}
+}
+
+/// how long a toast stays, in milliseconds
+const TOAST_MS: i32 = 2000;
+/// length of the shake of a rejected guess, keep in sync with index.css
+const SHAKE_MS: i32 = 600;
+/// until every tile of the last guess is flipped, keep in sync with index.css
+const REVEAL_MS: i32 = 1500;
+
+/// messages shown for a while, oldest first, and a function to show one
+#[hook]
+fn use_toasts() -> (Rc<RefCell<Vec<String>>>, Rc<dyn Fn(String)>) {
+	let toasts = use_mut_ref(Vec::<String>::new);
+	let force_update = use_force_update();
+	let notify = Rc::new({
+		let toasts = toasts.clone();
+		move |msg: String| {
+			toasts.borrow_mut().push(msg);
+			force_update.force_update();
+			let toasts = toasts.clone();
+			let force_update = force_update.clone();
+			set_timeout(TOAST_MS, move || {
+				toasts.borrow_mut().remove(0);
+				force_update.force_update();
+			});
+		}
+	});
+	return (toasts, notify);
+}
+
+fn toasts_view(toasts: &[String]) -> Html {
+	let toasts = toasts
+		.iter()
+		.map(|msg| html!(<div class="toast"> {msg} </div>))
+		.collect::<Html>();
+	return html!(<div class="toasts"> {toasts} </div>);
+}
+
+/// result as coloured squares, without letters to keep the answer secret
+fn share_text(plate: &Plate) -> String {
+	let score = match plate.is_win() {
+		true => plate.count().to_string(),
+		false => "X".to_string(),
+	};
+	let rows = plate.history().iter().map(|(_, state)| {
+		state
+			.iter()
+			.map(|s| match s {
+				LetterState::Correct => '🟩',
+				LetterState::Occured => '🟨',
+				_ => '⬛',
+			})
+			.collect::<String>()
+	});
+	return std::iter::once(format!("Wordle {}/{}", score, plate.max_guesses()))
+		.chain(rows)
+		.collect::<Vec<String>>()
+		.join("\n");
+}
+
+/// statistic kept in localStorage
+fn stored_statistic() -> Statistic {
+	load_item("statistic")
+		.and_then(|json| Statistic::load_from_json(&json).ok())
+		.unwrap_or_default()
+}
+
+/// statistic panel, `on_change` rerenders after it is imported into or cleared
+fn statistic_view(
+	statistic_ref: &Rc<RefCell<Statistic>>,
+	on_change: Callback<()>,
+	notify: Rc<dyn Fn(String)>,
+) -> Html {
+	let onclear = {
+		let statistic = statistic_ref.clone();
+		let on_change = on_change.clone();
+		Callback::from(move |e: MouseEvent| {
+			e.prevent_default();
+			let confirmed = window()
+				.and_then(|w| {
+					w.confirm_with_message("Clear all games? Export them first to keep a copy.")
+						.ok()
+				})
+				.unwrap_or(false);
+			if confirmed {
+				*statistic.borrow_mut() = Default::default();
+				store_item("statistic", "");
+				on_change.emit(());
+			}
+		})
+	};
+	// a `--state` file of the terminal version, or an export of another browser
+	let onimport = {
+		let statistic = statistic_ref.clone();
+		Callback::from(move |e: Event| {
+			let input: HtmlInputElement = match e.target_dyn_into() {
+				Some(input) => input,
+				None => return,
+			};
+			let (file, reader) = match (input.files().and_then(|f| f.get(0)), FileReader::new()) {
+				(Some(file), Ok(reader)) => (file, reader),
+				_ => return,
+			};
+			let onload = Closure::once_into_js({
+				let reader = reader.clone();
+				let statistic = statistic.clone();
+				let on_change = on_change.clone();
+				let notify = notify.clone();
+				move || {
+					let json = reader
+						.result()
+						.ok()
+						.and_then(|r| r.as_string())
+						.unwrap_or_default();
+					match Statistic::load_from_json(&json) {
+						Ok(imported) => {
+							let added = statistic.borrow_mut().merge(imported);
+							store_item("statistic", &statistic.borrow().store_to_json());
+							notify(format!("Imported {} games", added));
+							on_change.emit(());
+						}
+						Err(err) => notify(format!("invalid statistic: {}", err)),
+					}
+				}
+			});
+			reader.set_onload(Some(onload.unchecked_ref()));
+			let _ = reader.read_as_text(&file);
+			input.set_value("");
+		})
+	};
+	let statistic = statistic_ref.borrow();
+	let export = format!(
+		"data:application/json;charset=utf-8,{}",
+		String::from(encode_uri_component(&statistic.store_to_json()))
+	);
+	let timing = match statistic.average_time() {
+		Some(average) => html!(
+			<div class="statistic-row">
+				{format!("Average time: {:.1}s Fastest: {} Per guess: {:.1}s",
+					average.as_secs_f64(),
+					statistic.fastest_time().map_or("-".to_string(), |t| format!("{:.1}s", t.as_secs_f64())),
+					statistic.average_guess_time().unwrap_or_default().as_secs_f64())}
+			</div>
+		),
+		None => html!(),
+	};
+	return html!(
+		<div class="statistic">
+			<div class="statistic-row">
+				{format!("Total win: {} Total lose: {}: Average attempts: {:.2}",
+					statistic.success_cnt(),
+					statistic.fail_cnt(),
+					statistic.success_attempt_average())}
+			</div>
+			<div class="statistic-row">
+				{format!("Streak: {} Best streak: {}", statistic.current_streak(), statistic.max_streak())}
+			</div>
+			<div class="statistic-row">
+				{format!("Top words: {}",
+					statistic.top5_words().map(|x| format!("{}*{}", x.str, x.cnt)).collect::<Vec<String>>().join(" "))}
+			</div>
+			{timing}
+			<div class="statistic-row">
+				{format!("Achievements: {}",
+					statistic.achievements().iter().map(|a| a.name()).collect::<Vec<&str>>().join(", "))}
+			</div>
+			<div class="statistic-row">
+				<a href={export} download="statistic.json"> {"Export"} </a>
+				<label class="statistic-import">
+					{"Import"}
+					<input type="file" accept=".json,application/json" onchange={onimport} />
+				</label>
+				<a href="/" onclick={onclear}> {"Clear statistic"} </a>
+			</div>
+		</div>
+	);
+}
+
+/// config of a challenge in the URL fragment `#challenge=CODE`
+fn challenge_config() -> Option<Result<Config>> {
+	let hash = window()?.location().hash().ok()?;
+	let code = hash.strip_prefix("#challenge=")?.to_string();
+	return Some((|| -> Result<Config> {
+		let (set_acceptable, list_final) = load_lists(None, None)?;
+		let challenge = Challenge::decode(&code)?;
+		challenge.check(&set_acceptable)?;
+		return Ok(Config {
+			difficulty: challenge.difficulty,
+			max_guesses: challenge.max_guesses,
+			stats: true,
+			analyze: false,
+			achievements: true,
+			word_src: WordSrc::Select(challenge.answer),
+			set_acceptable,
+			set_final: Default::default(),
+			list_final,
+			state_src: None,
+			archive: false,
+			name: String::new(),
+			players: vec![],
+			alternate: false,
+			timed: false,
+			countdown: None,
+			time_attack: None,
+			command: None,
+		});
+	})());
+}
+
+/// link playing `config` as a challenge, for a selected answer
+fn challenge_link(config: &Config) -> Option<String> {
+	let answer = match config.word_src {
+		WordSrc::Select(answer) => answer,
+		_ => return None,
+	};
+	let code = Challenge::new(answer, config.difficulty, config.max_guesses)
+		.ok()?
+		.encode();
+	let location = window()?.location();
+	let page = format!("{}{}", location.origin().ok()?, location.pathname().ok()?);
+	return Some(format!("{}#challenge={}", page, code));
+}
+
+/// item of localStorage
+fn load_item(key: &str) -> Option<String> {
+	window()?.local_storage().ok()??.get_item(key).ok()?
+}
+
+fn store_item(key: &str, value: &str) {
+	let _ = (|| -> Option<()> { window()?.local_storage().ok()??.set_item(key, value).ok() })();
+}
+
+/// fields of FormConfig by name, kept in localStorage
+type Settings = BTreeMap<String, String>;
+
+/// settings that fit in a deep link, word lists are too long
+const QUERY_KEYS: [&str; 11] = [
+	"mode",
+	"word",
+	"seed",
+	"day",
+	"difficult",
+	"strict",
+	"analyze",
+	"timed",
+	"countdown",
+	"race",
+	"name",
+];
+
+fn stored_settings() -> Settings {
+	load_item("settings")
+		.and_then(|json| serde_json::from_str(&json).ok())
+		.unwrap_or_default()
+}
+
+/// settings in the query parameters of the current URL
+fn query_settings() -> Settings {
+	let params = window()
+		.and_then(|w| w.location().search().ok())
+		.and_then(|search| UrlSearchParams::new_with_str(&search).ok());
+	let params = match params {
+		Some(params) => params,
+		None => return Default::default(),
+	};
+	return QUERY_KEYS
+		.iter()
+		.filter_map(|&key| Some((key.to_string(), params.get(key)?)))
+		.collect();
+}
+
+/// query parameters starting the game of `settings`
+fn settings_query(settings: &Settings) -> String {
+	let params = UrlSearchParams::new().unwrap();
+	for key in QUERY_KEYS {
+		if let Some(value) = settings.get(key).filter(|v| !v.is_empty()) {
+			params.append(key, value);
+		}
+	}
+	return format!("?{}", String::from(params.to_string()));
+}
+
+/// key of the next day to play for `seed` in localStorage
+fn position_key(seed: u64) -> String {
+	format!("position-{}", seed)
+}
+
+/// query of `settings` with the day resolved by `config`, so that a bookmark replays the same game
+fn game_query(settings: &Settings, config: &Config) -> String {
+	let mut settings = settings.clone();
+	if let (WordSrc::Random(_, day), false) = (&config.word_src, config.archive) {
+		settings.insert("day".to_string(), day.to_string());
+	}
+	return settings_query(&settings);
+}
+
+fn parse_settings(settings: &Settings) -> Result<Config> {
+	let get = |key: &str| settings.get(key).map(String::as_str).unwrap_or_default();
+	let seed = match get("seed") {
+		"" => 0,
+		seed => seed
+			.parse::<u64>()
+			.map_err(|_| anyhow!("invalid random seed: {:?}", seed))?,
+	};
+	let list = |key: &str, builtin: &[&str]| match get(key) {
+		"" => builtin.join("\n"),
+		list => list.to_string(),
+	};
+	let set_acceptable: HashSet<Word> = parse_list(
+		"acceptable list",
+		&list("list_acceptable", builtin_words::ACCEPTABLE),
+	)?;
+	let list_final: Vec<Word> =
+		parse_list("final list", &list("list_final", builtin_words::FINAL))?;
+	check_subset(&list_final, &set_acceptable)?;
+
+	let day = match get("day") {
+		// continue where the last game of the seed stopped, unless the final list got shorter
+		"" => load_item(&position_key(seed))
+			.and_then(|day| day.parse().ok())
+			.filter(|&day| day >= 1 && day as usize <= list_final.len())
+			.unwrap_or(1),
+		day => day
+			.parse::<u32>()
+			.map_err(|_| anyhow!("invalid day: {:?}", day))?,
+	};
+	let word_src = match get("mode") {
+		"select" => WordSrc::Select(word_from_str(get("word"))?),
+		"archive" => WordSrc::Random(seed, day_at((Date::now() / 1000.0) as u64)),
+		"race" => WordSrc::Race(get("race").to_string()),
+		_ => WordSrc::Random(seed, day),
+	};
+	if let WordSrc::Random(_, day) = word_src {
+		if day == 0 || day as usize > list_final.len() {
+			return Err(anyhow!("day {} is out of the final list", day));
+		}
+	}
+
+	let countdown = match get("countdown") {
+		"" => None,
+		secs => Some(Duration::from_secs(secs.parse::<u64>()?)),
+	};
+
+	return Ok(Config {
+		difficulty: Difficulty::from_flags(get("difficult") == "on", get("strict") == "on"),
+		max_guesses: MAX_GUESSES,
+		stats: true,
+		analyze: get("analyze") == "on",
+		achievements: true,
+		word_src,
+		set_acceptable,
+		set_final: Default::default(),
+		list_final,
+		state_src: None,
+		archive: get("mode") == "archive",
+		name: get("name").to_string(),
+		players: vec![],
+		alternate: false,
+		timed: get("timed") == "on" || countdown.is_some(),
+		countdown,
+		time_attack: None,
+		command: None,
+	});
+}
+
+/// Screens of the app
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Route {
+	Play,
+	Settings,
+	Stats,
+	Archive,
+	Leaderboard,
+}
+
+impl Route {
+	const ALL: [Route; 5] = [
+		Route::Play,
+		Route::Settings,
+		Route::Stats,
+		Route::Archive,
+		Route::Leaderboard,
+	];
+
+	fn from_path(path: &str) -> Self {
+		match path.trim_end_matches('/') {
+			"/play" => Route::Play,
+			"/stats" => Route::Stats,
+			"/archive" => Route::Archive,
+			"/leaderboard" => Route::Leaderboard,
+			_ => Route::Settings,
+		}
+	}
+
+	fn path(&self) -> &'static str {
+		match self {
+			Route::Play => "/play",
+			Route::Settings => "/settings",
+			Route::Stats => "/stats",
+			Route::Archive => "/archive",
+			Route::Leaderboard => "/leaderboard",
+		}
+	}
+
+	fn title(&self) -> &'static str {
+		match self {
+			Route::Play => "Play",
+			Route::Settings => "Settings",
+			Route::Stats => "Statistics",
+			Route::Archive => "Archive",
+			Route::Leaderboard => "Leaderboard",
+		}
+	}
+
+	/// route of the current URL
+	fn current() -> Self {
+		let path = window().and_then(|w| w.location().pathname().ok());
+		Route::from_path(&path.unwrap_or_default())
+	}
+}
+
+/// go to `url` without reloading, `replace` keeps the history length
+fn set_url(url: &str, replace: bool) {
+	let _ = (|| -> Option<()> {
+		let history = window()?.history().ok()?;
+		match replace {
+			true => history.replace_state_with_url(&JsValue::NULL, "", Some(url)),
+			false => history.push_state_with_url(&JsValue::NULL, "", Some(url)),
+		}
+		.ok()
 	})();
 }
 
 enum WordleMsg {
-	SetConfig(Result<Config>),
+	/// settings submitted by FormConfig
+	Start(Settings),
+	Navigate(Route),
+	/// back or forward button of the browser
+	PopState,
+	/// the oldest toast is gone
+	Expire,
 }
 struct Wordle {
-	config: Option<Config>,
+	route:     Route,
+	config:    Option<Config>,
+	/// same as `use_toasts`, which a struct component cannot use
+	toasts:    Vec<String>,
+	_popstate: Closure<dyn FnMut()>,
+}
+
+impl Wordle {
+	fn notify(&mut self, ctx: &Context<Self>, msg: String) {
+		self.toasts.push(msg);
+		let link = ctx.link().clone();
+		set_timeout(TOAST_MS, move || link.send_message(WordleMsg::Expire));
+	}
+
+	/// game of the stored settings, overridden by the query parameters
+	fn load_config(&mut self, ctx: &Context<Self>) {
+		let mut settings = stored_settings();
+		settings.extend(query_settings());
+		match parse_settings(&settings) {
+			Ok(config) => self.config = Some(config),
+			Err(err) => {
+				self.notify(ctx, err.to_string());
+				self.config = None;
+				self.route = Route::Settings;
+				set_url(Route::Settings.path(), true);
+			}
+		}
+	}
 }
 
 impl Component for Wordle {
 	type Message = WordleMsg;
 	type Properties = ();
 
-	fn create(_: &Context<Self>) -> Self {
-		Self {
-			config: None
+	fn create(ctx: &Context<Self>) -> Self {
+		let popstate = Closure::<dyn FnMut()>::new({
+			let link = ctx.link().clone();
+			move || link.send_message(WordleMsg::PopState)
+		});
+		if let Some(w) = window() {
+			w.set_onpopstate(Some(popstate.as_ref().unchecked_ref()));
 		}
+		let mut result = Self {
+			route:     Route::current(),
+			config:    None,
+			toasts:    vec![],
+			_popstate: popstate,
+		};
+		match challenge_config() {
+			Some(Ok(config)) => {
+				result.route = Route::Play;
+				result.config = Some(config);
+			}
+			Some(Err(err)) => result.notify(ctx, err.to_string()),
+			None if result.route == Route::Play => result.load_config(ctx),
+			None => {}
+		}
+		return result;
 	}
 
-	fn update(&mut self, _: &Context<Self>, msg: Self::Message) -> bool {
+	fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
 		match msg {
-			WordleMsg::SetConfig(config) => match config {
-				Ok(config) => self.config = Some(config),
-				Err(err) => alert(&err.to_string()),
+			WordleMsg::Start(settings) => match parse_settings(&settings) {
+				Ok(config) => {
+					store_item("settings", &serde_json::to_string(&settings).unwrap());
+					set_url(
+						&format!("{}{}", Route::Play.path(), game_query(&settings, &config)),
+						false,
+					);
+					self.route = Route::Play;
+					self.config = Some(config);
+				}
+				Err(err) => self.notify(ctx, err.to_string()),
 			},
+			WordleMsg::Navigate(route) => {
+				let query = match (route, &self.config) {
+					(Route::Play, Some(config)) => game_query(&stored_settings(), config),
+					(Route::Play, None) => settings_query(&stored_settings()),
+					_ => String::new(),
+				};
+				set_url(&format!("{}{}", route.path(), query), false);
+				self.route = route;
+				if route == Route::Play && self.config.is_none() {
+					self.load_config(ctx);
+				}
+			}
+			WordleMsg::PopState => {
+				self.route = Route::current();
+				self.config = None;
+				if self.route == Route::Play {
+					self.load_config(ctx);
+				}
+			}
+			WordleMsg::Expire => {
+				self.toasts.remove(0);
+			}
 		}
 		return true;
 	}
 
 	fn view(&self, ctx: &Context<Self>) -> Html {
 		// Adapted from https://github.com/yewstack/yew/blob/dbdd3b78e1f0aada1834dec5c6ee83449db9d220/examples/communication_child_to_parent/src/parent.rs#L45
-		let set_config = ctx.link().callback(WordleMsg::SetConfig);
+		let navigate = |route: Route| {
+			ctx.link().callback(move |e: MouseEvent| {
+				e.prevent_default();
+				WordleMsg::Navigate(route)
+			})
+		};
+		let nav = Route::ALL
+			.iter()
+			.map(|&route| {
+				let class = match route == self.route {
+					true => "nav-link nav-current",
+					false => "nav-link",
+				};
+				html!(<a href={route.path()} {class} onclick={navigate(route)}> {route.title()} </a>)
+			})
+			.collect::<Html>();
 
-		return match &self.config {
-			None => html!(<FormConfig {set_config} />),
-			Some(config) => html!(<GameBoard config={config.clone()} />),
+		let page = match (self.route, &self.config) {
+			(Route::Settings, _) | (Route::Play, None) => html!(
+				<FormConfig settings={stored_settings()} on_submit={ctx.link().callback(WordleMsg::Start)} />
+			),
+			(Route::Stats, _) => html!(<StatsPage />),
+			(Route::Archive, _) => {
+				let mut settings = stored_settings();
+				settings.insert("mode".to_string(), "archive".to_string());
+				match parse_settings(&settings) {
+					Ok(config) => html!(<ArchivePage {config} />),
+					Err(err) => html!(<div class="config-row"> {err.to_string()} </div>),
+				}
+			}
+			(Route::Leaderboard, _) => html!(<LeaderboardPage />),
+			(Route::Play, Some(config)) => match &config.word_src {
+				WordSrc::Race(_) => html!(<RaceGame config={config.clone()} />),
+				_ if config.archive => html!(<ArchivePage config={config.clone()} />),
+				WordSrc::Random(..) => html!(<RandomRounds config={config.clone()} />),
+				_ => html!(
+					<>
+						<GameBoard config={config.clone()} />
+						{
+							match challenge_link(config) {
+								Some(link) => html!(
+									<div class="config-row"> {"Challenge a friend: "} <a href={link.clone()}> {link} </a> </div>
+								),
+								None => html!(),
+							}
+						}
+					</>
+				),
+			},
 		};
+		return html!(
+			<>
+				<nav class="nav"> {nav} </nav>
+				{toasts_view(&self.toasts)}
+				{page}
+			</>
+		);
 	}
 }
 
 #[derive(PartialEq, Properties)]
 struct FormConfigProps {
-	set_config: Callback<Result<Config>>,
+	/// initial values of the fields
+	settings:  Settings,
+	on_submit: Callback<Settings>,
 }
 
 #[function_component]
 fn FormConfig(props: &FormConfigProps) -> Html {
 	// Adapted from https://github.com/yewstack/yew/blob/dbdd3b78e1f0aada1834dec5c6ee83449db9d220/examples/communication_child_to_parent/src/child.rs#L26
-	let onsubmit = props.set_config.reform(|e: SubmitEvent| -> Result<Config> {
+	let onsubmit = props.on_submit.reform(|e: SubmitEvent| -> Settings {
 		e.prevent_default();
 		// Adapted from https://github.com/yewstack/yew/issues/474
 		let form: HtmlFormElement = e.target_unchecked_into();
 		let form = FormData::new_with_form(&form).unwrap();
-
-		let word_src = if form.get("word_src") == JsValue::from_str("select") {
-			let err = anyhow!("invalid word {:?}", form.get("word"));
-			WordSrc::Select(word_from_str(&form.get("word").as_string().ok_or(err)?)?)
-		} else {
-			let err = anyhow!("invalid random seed: {:?}", form.get("seed"));
-			WordSrc::Random(form.get("seed").as_string().ok_or(err)?.parse::<u64>()?, 1)
-		};
-
-		fn parse_list<T: FromIterator<Word>>(list: &str) -> Result<T> {
-			list.trim()
-				.split('\n')
-				.map(|str| word_from_str(str))
-				.collect()
-		}
-		let set_acceptable: HashSet<Word> = parse_list(&form.get("list_acceptable").as_string().unwrap())?;
-		let list_final: Vec<[char; 5]> = parse_list(&form.get("list_final").as_string().unwrap())?;
-		for word in &list_final {
-			if !set_acceptable.contains(word) {
-				return Err(anyhow!("word {} in final list, but not in acceptable list", word_to_str(word)));
-			}
-		}
-
-		return Ok(Config {
-			difficult: form.get("difficult") == JsValue::from_str("on"),
-			stats: true,
-			word_src,
-			set_acceptable,
-			set_final: Default::default(),
-			list_final,
-			state_src: None,
-		});
+		return QUERY_KEYS
+			.iter()
+			.chain(&["list_acceptable", "list_final"])
+			.filter(|&&key| key != "day")
+			.filter_map(|&key| Some((key.to_string(), form.get(key).as_string()?)))
+			.collect();
 	});
 
-	let default_acceptable = builtin_words::ACCEPTABLE.join("\n");
-	let default_final = builtin_words::FINAL.join("\n");
+	let get = |key: &str| props.settings.get(key).cloned().unwrap_or_default();
+	let is_on = |key: &str| get(key) == "on";
+	let mode = match get("mode").as_str() {
+		"" => "select".to_string(),
+		mode => mode.to_string(),
+	};
+	let option = |value: &'static str, label: &'static str| html!(<option {value} selected={mode == value}> {label} </option>);
+	let or_default = |key: &str, default: String| match get(key).as_str() {
+		"" => default,
+		value => value.to_string(),
+	};
 
 	return html!(
 		<form {onsubmit}>
 			<div class="config-row">
 			<label> {"Difficult mode: "} </label>
-			<input type="checkbox" name="difficult" />
+			<input type="checkbox" name="difficult" checked={is_on("difficult")} />
+			</div>
+
+			<div class="config-row">
+			<label> {"Strict difficult mode: "} </label>
+			<input type="checkbox" name="strict" checked={is_on("strict")} />
+			</div>
+
+			<div class="config-row">
+			<label> {"Analyze guesses: "} </label>
+			<input type="checkbox" name="analyze" checked={is_on("analyze")} />
+			</div>
+
+			<div class="config-row">
+			<label> {"Timed: "} </label>
+			<input type="checkbox" name="timed" checked={is_on("timed")} />
+			</div>
+
+			<div class="config-row">
+			<label> {"Seconds per guess: "} </label>
+			<input type="number" name="countdown" min="1" value={get("countdown")} />
 			</div>
 
 			<div class="config-row">
 			<label> {"Game mode: "} </label>
-			<select name="word_src">
-					<option value="select"> {"Select"} </option>
-					<option value="random"> {"Random"} </option>
+			<select name="mode">
+					{option("select", "Select")}
+					{option("random", "Random")}
+					{option("archive", "Archive")}
+					{option("race", "Race")}
 			</select>
 			</div>
 
 			<div class="config-row">
 			<label> {"Select Word: "} </label>
-			<input type="text" name="word" />
+			<input type="text" name="word" value={get("word")} />
 			</div>
 
 			<div class="config-row">
 			<label> {"Random seed: "} </label>
-			<input type="number" name="seed" value="0"/>
+			<input type="number" name="seed" value={or_default("seed", "0".to_string())} />
+			</div>
+
+			<div class="config-row">
+			<label> {"Race room: "} </label>
+			<input type="text" name="race" value={or_default("race", "ws://127.0.0.1:8001/race/lunch".to_string())} />
+			</div>
+
+			<div class="config-row">
+			<label> {"Racer name: "} </label>
+			<input type="text" name="name" value={get("name")} />
 			</div>
 
 			<div class="config-row">
 			<label> {"Acceptable list: "} </label>
 			<br />
-			<textarea name="list_acceptable" value={default_acceptable}/>
+			<textarea name="list_acceptable" value={or_default("list_acceptable", builtin_words::ACCEPTABLE.join("\n"))}/>
 			</div>
 
 			<div class="config-row">
 			<label> {"Final list: "} </label>
 			<br />
-			<textarea name="list_final" value={default_final}/>
+			<textarea name="list_final" value={or_default("list_final", builtin_words::FINAL.join("\n"))}/>
 			</div>
 
 			<div class="config-row">
@@ -149,10 +753,408 @@ fn FormConfig(props: &FormConfigProps) -> Html {
 }
 
 #[derive(PartialEq, Properties)]
-struct GameBoardProps {
+struct RandomRoundsProps {
+	config: Config,
+}
+
+/// rounds through the shuffled final list, like RepeatReader in the CLI
+#[function_component]
+fn RandomRounds(props: &RandomRoundsProps) -> Html {
+	let (seed, first) = match props.config.word_src {
+		WordSrc::Random(seed, day) => (seed, day),
+		_ => unreachable!(),
+	};
+	let len = props.config.list_final.len() as u32;
+	let day = use_state(|| first);
+	let finished = use_state(|| false);
+	// start over after the whole list
+	let next = match *day < len {
+		true => *day + 1,
+		false => 1,
+	};
+
+	let on_finish = {
+		let finished = finished.clone();
+		Callback::from(move |()| {
+			finished.set(true);
+			store_item(&position_key(seed), &next.to_string());
+		})
+	};
+	let play_again = {
+		let (day, finished) = (day.clone(), finished.clone());
+		Callback::from(move |_: MouseEvent| {
+			finished.set(false);
+			day.set(next);
+			// a bookmark starts this game
+			let mut settings = query_settings();
+			settings.insert("day".to_string(), next.to_string());
+			set_url(
+				&format!("{}{}", Route::Play.path(), settings_query(&settings)),
+				true,
+			);
+		})
+	};
+
+	let config = Config {
+		word_src: WordSrc::Random(seed, *day),
+		..props.config.clone()
+	};
+	return html!(
+		<>
+			<div class="statistic-row"> {format!("Game #{}", *day)} </div>
+			<GameBoard key={*day} {config} {on_finish} />
+			if *finished {
+				<div class="config-row">
+					<button onclick={play_again}> {"Play again"} </button>
+				</div>
+			}
+		</>
+	);
+}
+
+#[derive(PartialEq, Properties)]
+struct ArchivePageProps {
 	config: Config,
 }
 
+/// pick a past daily puzzle, then play it
+#[function_component]
+fn ArchivePage(props: &ArchivePageProps) -> Html {
+	let picked = use_state(|| -> Option<u32> { None });
+	let (seed, last) = match props.config.word_src {
+		WordSrc::Random(seed, day) => (seed, day),
+		_ => unreachable!(),
+	};
+
+	if let Some(day) = *picked {
+		let config = Config {
+			word_src: WordSrc::Random(seed, day),
+			..props.config.clone()
+		};
+		return html!(<GameBoard {config} />);
+	}
+
+	let days = archive(&props.config.list_final, seed, last, &stored_statistic())
+		.into_iter()
+		.rev()
+		.map(|d| {
+			let class = match d.status {
+				DayStatus::Won => "archive-day letterbox-correct",
+				DayStatus::Played => "archive-day letterbox-occured",
+				DayStatus::Unplayed => "archive-day",
+			};
+			let onclick = {
+				let picked = picked.clone();
+				move |e: MouseEvent| {
+					e.prevent_default();
+					picked.set(Some(d.day));
+				}
+			};
+			html!(<a href="/" {class} {onclick}> {d.day} </a>)
+		})
+		.collect::<Html>();
+	return html!(
+		<div class="archive">
+			<div class="statistic-row"> {"Pick a day to replay"} </div>
+			{days}
+		</div>
+	);
+}
+
+/// statistic of all games in this browser
+#[function_component]
+fn StatsPage() -> Html {
+	let statistic = use_mut_ref(stored_statistic);
+	let (toasts, notify) = use_toasts();
+	let force_update = use_force_update();
+	let on_change = Callback::from(move |()| force_update.force_update());
+	return html!(
+		<div class="app">
+			{toasts_view(&toasts.borrow())}
+			{statistic_view(&statistic, on_change, notify)}
+		</div>
+	);
+}
+
+/// leaderboard exported by `wordle leaderboard --json`
+#[function_component]
+fn LeaderboardPage() -> Html {
+	let leaderboard = use_state(|| -> Option<Leaderboard> { None });
+	let (toasts, notify) = use_toasts();
+
+	let onsubmit = {
+		let leaderboard = leaderboard.clone();
+		let notify = notify.clone();
+		Callback::from(move |e: SubmitEvent| {
+			e.prevent_default();
+			let form: HtmlFormElement = e.target_unchecked_into();
+			let form = FormData::new_with_form(&form).unwrap();
+			let json = form.get("leaderboard").as_string().unwrap_or_default();
+			match Leaderboard::load_from_json(&json) {
+				Ok(result) => leaderboard.set(Some(result)),
+				Err(err) => notify(format!("invalid leaderboard: {}", err)),
+			}
+		})
+	};
+
+	let table = match leaderboard.as_ref() {
+		None => html!(),
+		Some(l) => {
+			let rows = l
+				.entries
+				.iter()
+				.enumerate()
+				.map(|(i, e)| {
+					html!(
+						<tr>
+							<td> {i + 1} </td>
+							<td> {&e.user} </td>
+							<td> {e.games} </td>
+							<td> {e.wins} </td>
+							<td> {format!("{:.1}%", e.win_rate * 100.0)} </td>
+							<td> {format!("{:.2}", e.average_attempts)} </td>
+							<td> {e.current_streak} </td>
+							<td> {e.max_streak} </td>
+						</tr>
+					)
+				})
+				.collect::<Html>();
+			let solves = match &l.daily {
+				None => html!(),
+				Some(daily) => html!(
+					<>
+						<div class="statistic-row"> {format!("Fastest solves of {}", daily)} </div>
+						{
+							l.solves
+								.iter()
+								.enumerate()
+								.map(|(i, solve)| html!(
+									<div class="statistic-row">
+										{format!("{}. {} in {}", i + 1, solve.user, solve.guesses)}
+									</div>
+								))
+								.collect::<Html>()
+						}
+					</>
+				),
+			};
+			html!(
+				<div class="analysis">
+					<table>
+						<tr>
+							<th> {"Rank"} </th>
+							<th> {"User"} </th>
+							<th> {"Games"} </th>
+							<th> {"Wins"} </th>
+							<th> {"Win rate"} </th>
+							<th> {"Average"} </th>
+							<th> {"Streak"} </th>
+							<th> {"Best streak"} </th>
+						</tr>
+						{rows}
+					</table>
+					{solves}
+				</div>
+			)
+		}
+	};
+
+	return html!(
+		<div class="app">
+			{toasts_view(&toasts.borrow())}
+			<form {onsubmit}>
+				<div class="config-row">
+				<label> {"Leaderboard JSON: "} </label>
+				<br />
+				<textarea name="leaderboard" />
+				</div>
+				<div class="config-row">
+				<input type="submit" value="Load" />
+				</div>
+			</form>
+			{table}
+		</div>
+	);
+}
+
+#[derive(PartialEq, Properties)]
+struct RaceGameProps {
+	config: Config,
+}
+
+/// join a race room, then play its shared answer
+#[function_component]
+fn RaceGame(props: &RaceGameProps) -> Html {
+	let start = use_state(|| -> Option<(u64, u32)> { None });
+	let waiting = use_state(|| -> (Vec<String>, usize) { (vec![], 0) });
+	let players = use_state(|| -> Vec<Racer> { vec![] });
+	let socket = use_mut_ref(|| -> Option<WebSocket> { None });
+	let (toasts, notify) = use_toasts();
+
+	{
+		let (start, waiting, players, socket, notify) = (
+			start.clone(),
+			waiting.clone(),
+			players.clone(),
+			socket.clone(),
+			notify.clone(),
+		);
+		let config = props.config.clone();
+		use_effect_with((), move |_| {
+			let url = match &config.word_src {
+				WordSrc::Race(url) => url.clone(),
+				_ => unreachable!(),
+			};
+			match WebSocket::new(&url) {
+				Err(_) => notify(format!("cannot connect to {}", url)),
+				Ok(ws) => {
+					let join = serde_json::to_string(&ClientMsg::Join {
+						name: config.name.clone(),
+					})
+					.unwrap();
+					let onopen = Closure::<dyn FnMut()>::new({
+						let ws = ws.clone();
+						move || {
+							let _ = ws.send_with_str(&join);
+						}
+					});
+					let onmessage =
+						Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+							let msg = e
+								.data()
+								.as_string()
+								.and_then(|text| serde_json::from_str::<ServerMsg>(&text).ok());
+							match msg {
+								Some(ServerMsg::Waiting {
+									players,
+									needed,
+								}) => waiting.set((players, needed)),
+								Some(ServerMsg::Start {
+									seed,
+									day,
+								}) => start.set(Some((seed, day))),
+								Some(ServerMsg::Board {
+									players: board,
+								}) => players.set(board),
+								Some(ServerMsg::Error {
+									message,
+								}) => notify(message),
+								None => {}
+							}
+						});
+					ws.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+					ws.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+					onopen.forget();
+					onmessage.forget();
+					*socket.borrow_mut() = Some(ws);
+				}
+			}
+			move || {
+				if let Some(ws) = socket.borrow_mut().take() {
+					let _ = ws.close();
+				}
+			}
+		});
+	}
+
+	let on_guess = {
+		let socket = socket.clone();
+		Callback::from(move |state: WordState| {
+			if let Some(ws) = socket.borrow().as_ref() {
+				let msg = ClientMsg::Guess {
+					state: state_to_str(&state),
+				};
+				let _ = ws.send_with_str(&serde_json::to_string(&msg).unwrap());
+			}
+		})
+	};
+
+	let board = match *start {
+		None => html!(
+			<div class="race-waiting">
+				{format!("Waiting for racers: {} ({}/{})", waiting.0.join(", "), waiting.0.len(), waiting.1)}
+			</div>
+		),
+		Some((seed, day)) => {
+			let config = Config {
+				word_src: WordSrc::Random(seed, day),
+				..props.config.clone()
+			};
+			html!(
+				<>
+					<RaceBoard players={(*players).clone()} />
+					<GameBoard {config} {on_guess} />
+				</>
+			)
+		}
+	};
+	return html!(
+		<>
+			{toasts_view(&toasts.borrow())}
+			{board}
+		</>
+	);
+}
+
+#[derive(PartialEq, Properties)]
+struct RaceBoardProps {
+	players: Vec<Racer>,
+}
+
+/// colour-only progress of every racer
+#[function_component]
+fn RaceBoard(props: &RaceBoardProps) -> Html {
+	let rows = ranking(&props.players)
+		.into_iter()
+		.enumerate()
+		.map(|(i, r)| {
+			let status = match (r.is_win(), r.is_over(), r.left) {
+				(true, _, _) => format!("{}. won", i + 1),
+				(false, _, true) => "left".to_string(),
+				(false, true, false) => format!("{}. failed", i + 1),
+				(false, false, false) => format!("{}/{}", r.states.len(), MAX_GUESSES),
+			};
+			let states = r
+				.states
+				.iter()
+				.map(|state| {
+					let blocks = state
+						.chars()
+						.map(|c| {
+							let class = match c {
+								'G' => "race-block letterbox-correct",
+								'Y' => "race-block letterbox-occured",
+								_ => "race-block letterbox-redundant",
+							};
+							html!(<span {class} />)
+						})
+						.collect::<Html>();
+					html!(<span class="race-state"> {blocks} </span>)
+				})
+				.collect::<Html>();
+			html!(
+				<div class="race-row">
+					<span class="race-name"> {&r.name} </span>
+					{states}
+					<span class="race-status"> {status} </span>
+				</div>
+			)
+		})
+		.collect::<Html>();
+	html!(<div class="race"> {rows} <hr /> </div>)
+}
+
+#[derive(PartialEq, Properties)]
+struct GameBoardProps {
+	config:    Config,
+	/// feedback of every accepted guess
+	#[prop_or_default]
+	on_guess:  Callback<WordState>,
+	/// the game is over and recorded
+	#[prop_or_default]
+	on_finish: Callback<()>,
+}
+
 #[function_component]
 fn GameBoard(props: &GameBoardProps) -> Html {
 	let goal = match &props.config.word_src {
@@ -163,79 +1165,286 @@ fn GameBoard(props: &GameBoardProps) -> Html {
 		_ => unreachable!(),
 	};
 	let update_flag = use_state(|| 0);
-	let plate = use_mut_ref(|| Plate::new(goal, props.config.difficult));
-	let statistic = use_mut_ref(|| {
-		let result = (|| -> Option<Statistic> {
-			let storage = window()?.local_storage().ok()??;
-			let statistic_json = storage.get_item("statistic").ok()??;
-			Statistic::load_from_json(&statistic_json).ok()
-		})();
-		result.unwrap_or(Default::default())
+	let plate = use_mut_ref(|| {
+		Plate::with_max_guesses(goal, props.config.difficulty, props.config.max_guesses)
 	});
+	let statistic = use_mut_ref(stored_statistic);
 
-	let statistic_store = |statistic: &Statistic| {
-		let _ = (|| -> Option<()> {
-			let storage = window()?.local_storage().ok()??;
-			let _ = storage.set_item("statistic", &statistic.store_to_json());
-			return None;
-		})();
-	};
-	let statistic_clear = {
+	let statistic_store =
+		|statistic: &Statistic| store_item("statistic", &statistic.store_to_json());
+	let statistic_change = {
 		let update_flag = update_flag.clone();
+		Callback::from(move |()| update_flag.set(*update_flag ^ 1))
+	};
+
+	let analysis = use_mut_ref(|| -> Option<Analysis> { None });
+	// the analysis of the finished game is pending
+	let analyzing = use_mut_ref(|| false);
+	// start of the current guess, in milliseconds
+	let guess_start = use_mut_ref(Date::now);
+	let force_update = use_force_update();
+
+	// messages shown over the plate
+	let (toasts, notify) = use_toasts();
+	// the current guess is shaking after being rejected
+	let shake = use_mut_ref(|| false);
+	let reject = Rc::new({
+		let shake = shake.clone();
+		let notify = notify.clone();
+		let force_update = force_update.clone();
+		move |msg: String| {
+			*shake.borrow_mut() = true;
+			notify(msg);
+			let shake = shake.clone();
+			let force_update = force_update.clone();
+			set_timeout(SHAKE_MS, move || {
+				*shake.borrow_mut() = false;
+				force_update.force_update();
+			});
+		}
+	});
+	// the end of game dialog is open
+	let modal = use_mut_ref(|| false);
+	let unlocked = use_mut_ref(Vec::<Achievement>::new);
+
+	let record = Rc::new({
+		let plate = plate.clone();
+		let config = props.config.clone();
 		let statistic = statistic.clone();
-		move |e: MouseEvent| {
-			e.prevent_default();
-			update_flag.set(*update_flag ^ 1);
-			*statistic.borrow_mut() = Default::default();
-			let _ = (|| -> Option<()> {
-				let storage = window()?.local_storage().ok()??;
-				let _ = storage.set_item("statistic", "");
-				return None;
-			})();
+		let analysis = analysis.clone();
+		let on_finish = props.on_finish.clone();
+		let unlocked = unlocked.clone();
+		move || {
+			let plate = plate.borrow();
+			match config.analyze {
+				true => {
+					let result = analyze(&plate, &config.list_final, &config.set_acceptable, None);
+					statistic
+						.borrow_mut()
+						.add_analyzed_plate(&plate, result.clone());
+					*analysis.borrow_mut() = Some(result);
+				}
+				false => statistic.borrow_mut().add_plate(&plate),
+			}
+			if let (true, WordSrc::Random(_, day)) = (config.archive, &config.word_src) {
+				statistic.borrow_mut().mark_archived(*day);
+			}
+			if config.achievements {
+				*unlocked.borrow_mut() = statistic.borrow_mut().unlock_achievements(&plate);
+			}
+			statistic_store(&statistic.borrow());
+			on_finish.emit(());
 		}
-	};
+	});
+	let finish = Rc::new({
+		let analyzing = analyzing.clone();
+		let analyze = props.config.analyze;
+		let modal = modal.clone();
+		let force_update = force_update.clone();
+		move || {
+			// the analysis blocks the page for a while, so it waits for the last guess to be revealed
+			match analyze {
+				true => {
+					*analyzing.borrow_mut() = true;
+					force_update.force_update();
+				}
+				false => record(),
+			}
+			let record = record.clone();
+			let analyzing = analyzing.clone();
+			let modal = modal.clone();
+			let force_update = force_update.clone();
+			set_timeout(REVEAL_MS, move || {
+				if analyze {
+					record();
+					*analyzing.borrow_mut() = false;
+				}
+				*modal.borrow_mut() = true;
+				force_update.force_update();
+			});
+		}
+	});
 
-	let send_word = Rc::new(Cell::new(Callback::from({
+	{
+		let plate = plate.clone();
+		let guess_start = guess_start.clone();
+		let finish = finish.clone();
+		let force_update = force_update.clone();
+		let countdown = props.config.countdown;
+		use_effect_with((), move |_| {
+			let tick = Closure::<dyn FnMut()>::new(move || {
+				let countdown = match countdown {
+					Some(countdown) => countdown,
+					None => return,
+				};
+				if plate.borrow().is_over() {
+					return;
+				}
+				if Date::now() - *guess_start.borrow() > countdown.as_millis() as f64 {
+					plate.borrow_mut().time_up();
+					finish();
+				}
+				force_update.force_update();
+			});
+			let interval = window().and_then(|w| {
+				w.set_interval_with_callback_and_timeout_and_arguments_0(
+					tick.as_ref().unchecked_ref(),
+					1000,
+				)
+				.ok()
+			});
+			move || {
+				if let (Some(w), Some(interval)) = (window(), interval) {
+					w.clear_interval_with_handle(interval);
+				}
+				drop(tick);
+			}
+		});
+	}
+
+	// letters typed for the next guess
+	let letters = use_mut_ref(Vec::<Letter>::new);
+
+	let send_word = Callback::from({
 		let update_flag = update_flag.clone();
+		let letters = letters.clone();
 		let plate = plate.clone();
-		let set_acceptable = props.config.set_acceptable.clone();
-		let statistic = statistic.clone();
+		let config = props.config.clone();
+		let on_guess = props.on_guess.clone();
+		let guess_start = guess_start.clone();
+		let finish = finish.clone();
+		let reject = reject.clone();
 		move |word: Word| {
-			if set_acceptable.contains(&word) {
+			if config.set_acceptable.contains(&word) {
 				update_flag.set(*update_flag ^ 1);
-				let res = plate.borrow_mut().guess(&word);
-				if let Err(err) = res {
-					alert(&err.to_string());
+				let elapsed =
+					Duration::from_millis((Date::now() - *guess_start.borrow()).max(0.0) as u64);
+				let res = match config.timed {
+					true => plate.borrow_mut().guess_timed(&word, elapsed),
+					false => plate.borrow_mut().guess(&word),
+				};
+				match res {
+					Ok(()) => {
+						letters.borrow_mut().clear();
+						*guess_start.borrow_mut() = Date::now();
+						on_guess.emit(plate.borrow().history().last().unwrap().1)
+					}
+					Err(err) => reject(err.to_string()),
 				}
-				// TODO: move alert to appropriate time
-				if plate.borrow().is_win() {
-					statistic.borrow_mut().add_plate(&plate.borrow());
-					statistic_store(&statistic.borrow());
-					alert("You win!");
-				} else if plate.borrow().history().len() == 6 {
-					statistic.borrow_mut().add_plate(&plate.borrow());
-					statistic_store(&statistic.borrow());
-					alert(&format!(
-						"You lose! Anwer is {}",
-						word_to_str(plate.borrow().goal())
-					));
+				if plate.borrow().is_over() {
+					finish();
 				}
 			} else {
-				alert(&format!("{} is not in acceptable list", word_to_str(&word)));
+				reject(GuessError::NotAcceptable(word).to_string());
 			}
 		}
-	})));
+	});
+
+	// key names of KeyboardEvent::key(), from a physical or the on-screen keyboard
+	let on_key = {
+		let update_flag = update_flag.clone();
+		let letters = letters.clone();
+		let plate = plate.clone();
+		let reject = reject.clone();
+		Callback::from(move |key: String| {
+			if plate.borrow().is_over() {
+				return;
+			}
+			match key.as_str() {
+				"Enter" => {
+					let len = letters.borrow().len();
+					let word: Option<[Letter; 5]> = letters.borrow().clone().try_into().ok();
+					match word.map(Word::new) {
+						Some(Ok(word)) => send_word.emit(word),
+						_ => reject(WordError::Length(len).to_string()),
+					}
+				}
+				"Backspace" => {
+					letters.borrow_mut().pop();
+				}
+				key => {
+					let mut chars = key.chars();
+					if let (Some(c), None) = (chars.next(), chars.next()) {
+						if c.is_ascii_alphabetic() && letters.borrow().len() < 5 {
+							letters.borrow_mut().push(c.to_ascii_uppercase());
+						}
+					}
+				}
+			}
+			update_flag.set(*update_flag ^ 1);
+		})
+	};
 
+	let countdown = match props.config.countdown {
+		Some(countdown) if !plate.borrow().is_over() => {
+			let left = countdown.as_secs_f64() - (Date::now() - *guess_start.borrow()) / 1000.0;
+			html!(<div class="countdown"> {format!("{:.0}s left", left.max(0.0))} </div>)
+		}
+		_ => html!(),
+	};
+	let modal_view = match *modal.borrow() {
+		true => {
+			let plate = plate.borrow();
+			let statistic = statistic.borrow();
+			let title = match (plate.is_win(), plate.is_time_up()) {
+				(true, _) => "You win!",
+				(false, true) => "Time up!",
+				(false, false) => "You lose!",
+			};
+			let played = statistic.success_cnt() + statistic.fail_cnt();
+			let achievements = unlocked
+				.borrow()
+				.iter()
+				.map(|a| {
+					html!(
+						<div class="modal-row">
+							{format!("Achievement unlocked: {} - {}", a.name(), a.description())}
+						</div>
+					)
+				})
+				.collect::<Html>();
+			let close = {
+				let modal = modal.clone();
+				let force_update = force_update.clone();
+				Callback::from(move |_: MouseEvent| {
+					*modal.borrow_mut() = false;
+					force_update.force_update();
+				})
+			};
+			html!(
+				<div class="modal">
+					<div class="modal-content">
+						<h2> {title} </h2>
+						<div class="modal-row"> {format!("Answer: {}", word_to_str(plate.goal()))} </div>
+						<div class="modal-row">
+							{format!("Played: {} Win: {:.0}% Streak: {} Best streak: {}",
+								played,
+								statistic.success_cnt() as f64 * 100.0 / played.max(1) as f64,
+								statistic.current_streak(),
+								statistic.max_streak())}
+						</div>
+						{achievements}
+						<textarea class="modal-share" readonly=true value={share_text(&plate)} />
+						<button onclick={close}> {"Close"} </button>
+					</div>
+				</div>
+			)
+		}
+		false => html!(),
+	};
 	return html!(
 		<div class="app">
+			{toasts_view(&toasts.borrow())}
+			{modal_view}
+			{countdown}
 			<div class="plate">
 			{
-				(0..6usize).into_iter()
+				(0..plate.borrow().max_guesses() as usize).into_iter()
 					.map(|id| {
 						if id < plate.borrow().history().len() {
-							html!( <WordColor ws={plate.borrow().history()[id]} />)
-						} else if id == plate.borrow().history().len() && !plate.borrow().is_win() {
-							html!( <WordInput send_word={send_word.take()} /> )
+							html!( <WordColor ws={plate.borrow().history()[id]} reveal=true />)
+						} else if id == plate.borrow().history().len() && !plate.borrow().is_over() {
+							html!( <WordInput letters={letters.borrow().clone()} on_key={on_key.clone()} shake={*shake.borrow()} /> )
 						} else {
 							html!( <WordBlank /> )
 						}
@@ -244,23 +1453,17 @@ fn GameBoard(props: &GameBoardProps) -> Html {
 			}
 			</div>
 			<hr />
-			<Keyboard keyboard={plate.borrow().keyboard().clone()} />
+			<Keyboard keyboard={plate.borrow().keyboard().clone()} {on_key} />
+			<Hint knowledge={plate.borrow().knowledge().clone()} keyboard={plate.borrow().keyboard().clone()} />
 			<hr />
-			<div class="statistic">
-				<div class="statistic-row">
-					{format!("Total win: {} Total lose: {}: Average attempts: {:.2}",
-						statistic.borrow().success_cnt(),
-						statistic.borrow().fail_cnt(),
-						statistic.borrow().success_attempt_average())}
-				</div>
-				<div class="statistic-row">
-					{format!("Top words: {}",
-						statistic.borrow().top5_words().map(|x| format!("{}*{}", x.str, x.cnt)).collect::<Vec<String>>().join(" "))}
-				</div>
-				<div class="statistic-row">
-					<a href="/" onclick={statistic_clear}> {"Clear statistic"} </a>
-				</div>
-			</div>
+			{
+				match analysis.borrow().as_ref() {
+					Some(analysis) => html!(<><AnalysisView analysis={analysis.clone()} /><hr /></>),
+					None if *analyzing.borrow() => html!(<><div class="analysis"> {"Analyzing..."} </div><hr /></>),
+					None => html!(),
+				}
+			}
+			{statistic_view(&statistic, statistic_change, notify.clone())}
 		</div>
 	);
 }
@@ -268,27 +1471,136 @@ fn GameBoard(props: &GameBoardProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct KeyboardProps {
 	keyboard: LetterMap<LetterState>,
+	/// name of the clicked key as in KeyboardEvent::key()
+	#[prop_or_default]
+	on_key:   Callback<String>,
 }
 
+/// letter colours, clickable for touch devices
 #[function_component]
 fn Keyboard(props: &KeyboardProps) -> Html {
 	let get_row = |s: &str| {
 		s.chars()
-			.map(|c| html!(<LetterColor {c} s={props.keyboard[c]} />))
+			.map(|c| {
+				let onclick = props.on_key.reform(move |_: MouseEvent| c.to_string());
+				html!(
+					<div class="keyboard-key" {onclick}>
+						<LetterColor {c} s={props.keyboard[c]} />
+					</div>
+				)
+			})
 			.collect::<Html>()
 	};
+	let action = |key: &'static str, label: &'static str| {
+		let onclick = props.on_key.reform(move |_: MouseEvent| key.to_string());
+		html!(<button class="keyboard-action" {onclick}> {label} </button>)
+	};
 	html!(
 		<div class="keyboard">
 			<div class="keyboard-row"> {get_row("QWERTYUIOP")} </div>
 			<div class="keyboard-row"> {get_row("ASDFGHJKL")} </div>
-			<div class="keyboard-row"> {get_row("ZXCVBNM")} </div>
+			<div class="keyboard-row">
+				{action("Enter", "Enter")}
+				{get_row("ZXCVBNM")}
+				{action("Backspace", "⌫")}
+			</div>
+		</div>
+	)
+}
+
+#[derive(PartialEq, Properties)]
+struct AnalysisViewProps {
+	analysis: Analysis,
+}
+
+/// post-mortem of a finished game
+#[function_component]
+fn AnalysisView(props: &AnalysisViewProps) -> Html {
+	let rows = props
+		.analysis
+		.guesses
+		.iter()
+		.map(|g| {
+			html!(
+				<tr>
+					<td> {&g.guess} </td>
+					<td> {g.before} </td>
+					<td> {g.after} </td>
+					<td> {format!("{:.2}", g.expected)} </td>
+					<td> {format!("{} ({:.2})", g.best_guess, g.best_expected)} </td>
+					<td> {format!("{:.0}", g.skill)} </td>
+					<td> {format!("{:+.2}", g.luck)} </td>
+				</tr>
+			)
+		})
+		.collect::<Html>();
+	html!(
+		<div class="analysis">
+			<table>
+				<tr>
+					<th> {"Guess"} </th>
+					<th> {"Before"} </th>
+					<th> {"After"} </th>
+					<th> {"Expected"} </th>
+					<th> {"Best guess"} </th>
+					<th> {"Skill"} </th>
+					<th> {"Luck"} </th>
+				</tr>
+				{rows}
+			</table>
+			<div class="statistic-row">
+				{format!("Skill: {:.0} Luck: {:+.2}", props.analysis.skill, props.analysis.luck)}
+			</div>
+		</div>
+	)
+}
+
+#[derive(PartialEq, Properties)]
+struct HintProps {
+	knowledge: Knowledge,
+	keyboard:  LetterMap<LetterState>,
+}
+
+/// allowed letters of each position
+#[function_component]
+fn Hint(props: &HintProps) -> Html {
+	let get_row = |i: usize| {
+		props
+			.knowledge
+			.allowed(i)
+			.map(|c| {
+				let class = match props.keyboard[c] {
+					LetterState::Correct => "hint-letter letterbox-correct",
+					LetterState::Occured => "hint-letter letterbox-occured",
+					LetterState::Redundant => "hint-letter letterbox-redundant",
+					LetterState::Unknown => "hint-letter letterbox-unknown",
+				};
+				html!(<span {class}> {c.to_string()} </span>)
+			})
+			.collect::<Html>()
+	};
+	html!(
+		<div class="hint">
+			{
+				(0..5usize)
+					.map(|i| html!(
+						<div class="hint-row">
+							<span class="hint-position"> {format!("{}:", i + 1)} </span>
+							{get_row(i)}
+						</div>
+					))
+					.collect::<Html>()
+			}
 		</div>
 	)
 }
 
 #[derive(PartialEq, Properties)]
 struct WordColorProps {
-	ws: (Word, WordState),
+	ws:     (Word, WordState),
+	/// flip the tiles one by one when shown
+	#[prop_or_default]
+	reveal: bool,
 }
 
 #[function_component]
@@ -298,56 +1610,48 @@ fn WordColor(props: &WordColorProps) -> Html {
 		.0
 		.iter()
 		.zip(props.ws.1.iter())
-		.map(|(c, s)| html!(<LetterColor c={*c} s={*s} />))
+		.enumerate()
+		.map(|(i, (c, s))| {
+			let flip = props.reveal.then_some(i);
+			html!(<LetterColor c={*c} s={*s} {flip} />)
+		})
 		.collect();
 	return html!( <div class="plate-row"> { children } </div> );
 }
 
 #[derive(PartialEq, Properties)]
 struct WordInputProps {
-	send_word: Callback<Word>,
+	letters: Vec<Letter>,
+	/// name of the pressed key
+	on_key:  Callback<String>,
+	/// the guess was rejected
+	#[prop_or_default]
+	shake:   bool,
 }
 
 #[function_component]
 fn WordInput(props: &WordInputProps) -> Html {
-	let update_flag = use_state(|| 0);
-	let letters = use_mut_ref(|| -> Vec<Letter> { Default::default() });
-
 	let children: Vec<Html> = (0..5usize)
-		.map(|id| {
-			if id < letters.borrow().len() {
-				html!(<LetterColor c={letters.borrow()[id]} s={LetterState::Unknown} />)
-			} else {
-				html!(<LetterColor c={' '} s={LetterState::Unknown} />)
-			}
+		.map(|id| match props.letters.get(id) {
+			Some(&c) => html!(<LetterColor {c} s={LetterState::Unknown} />),
+			None => html!(<LetterColor c={' '} s={LetterState::Unknown} />),
 		})
 		.collect();
 
-	let onkeydown = {
-		let update_flag = update_flag.clone();
-		let letters = letters.clone();
-		let send_word = props.send_word.clone();
-		Callback::from(move |e: KeyboardEvent| {
-			let code = e.key_code();
-			if 65 <= code && code <= 90 {
-				let c = code as u8 as char;
-				if letters.borrow().len() < 5 {
-					update_flag.set(*update_flag ^ 1);
-					letters.borrow_mut().push(c);
-				}
-			} else if code == 8 {
-				update_flag.set(*update_flag ^ 1);
-				letters.borrow_mut().pop();
-			} else if code == 13 {
-				if letters.borrow().len() == 5 {
-					let word: Word = letters.borrow().clone().try_into().unwrap();
-					send_word.emit(word);
-				}
-			}
-		})
-	};
+	// browser shortcuts and keys ignored by the game keep their default action
+	let on_key = props.on_key.clone();
+	let onkeydown = Callback::from(move |e: KeyboardEvent| {
+		let key = e.key();
+		let handled = matches!(key.as_str(), "Enter" | "Backspace")
+			|| (key.len() == 1 && key.chars().all(|c| c.is_ascii_alphabetic()));
+		if handled && !(e.ctrl_key() || e.meta_key() || e.alt_key()) {
+			e.prevent_default();
+			on_key.emit(key);
+		}
+	});
 
-	return html!( <div class="plate-row"> { children } <input {onkeydown} id={"focus-me"}/> </div> );
+	let class = classes!("plate-row", props.shake.then_some("shake"));
+	return html!( <div {class}> { children } <input {onkeydown} id={"focus-me"} inputmode="none" /> </div> );
 }
 
 #[function_component]
@@ -360,26 +1664,26 @@ fn WordBlank() -> Html {
 
 #[derive(PartialEq, Properties)]
 struct LetterColorProps {
-	c: Letter,
-	s: LetterState,
+	c:    Letter,
+	s:    LetterState,
+	/// position in a revealed guess, which delays its flip
+	#[prop_or_default]
+	flip: Option<usize>,
 }
 
 #[function_component]
 fn LetterColor(props: &LetterColorProps) -> Html {
-	match props.s {
-		LetterState::Correct => {
-			html!(<div class="letterbox letterbox-correct">   {props.c.to_string()} </div>)
-		}
-		LetterState::Occured => {
-			html!(<div class="letterbox letterbox-occured">   {props.c.to_string()} </div>)
-		}
-		LetterState::Redundant => {
-			html!(<div class="letterbox letterbox-redundant"> {props.c.to_string()} </div>)
-		}
-		LetterState::Unknown => {
-			html!(<div class="letterbox letterbox-unknown">    {props.c.to_string()} </div>)
-		}
-	}
+	let state = match props.s {
+		LetterState::Correct => "letterbox-correct",
+		LetterState::Occured => "letterbox-occured",
+		LetterState::Redundant => "letterbox-redundant",
+		LetterState::Unknown => "letterbox-unknown",
+	};
+	let class = classes!("letterbox", state, props.flip.map(|_| "flip"));
+	let style = props
+		.flip
+		.map(|i| format!("animation-delay: {}ms", i * 250));
+	html!(<div {class} {style}> {props.c.to_string()} </div>)
 }
 
 fn main() {
@@ -0,0 +1,158 @@
+use {
+	crate::plate::*,
+	std::{collections::HashSet, fmt},
+};
+
+/// A custom puzzle shared as a code instead of a plain text answer
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Challenge {
+	pub answer:      Word,
+	pub difficulty:  Difficulty,
+	pub max_guesses: u32,
+}
+
+/// Reasons for rejecting a challenge
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ChallengeError {
+	/// not a code of this version, e.g. mistyped
+	Malformed,
+	/// words of this length are not supported
+	WordLength(u32),
+	/// max guesses out of 1 ~ 15
+	MaxGuesses(u32),
+	/// answer is not in the acceptable list
+	NotAcceptable(Word),
+}
+
+impl fmt::Display for ChallengeError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ChallengeError::Malformed => write!(f, "malformed challenge code"),
+			ChallengeError::WordLength(len) => write!(f, "unsupported word length {}", len),
+			ChallengeError::MaxGuesses(n) => {
+				write!(f, "max guesses must be between 1 and 15, found {}", n)
+			}
+			ChallengeError::NotAcceptable(word) => write!(f, "{} is not an acceptable word", word),
+		}
+	}
+}
+
+impl std::error::Error for ChallengeError {}
+
+/// Crockford base32, without I, L, O and U
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// 9 base32 digits
+const BITS: u32 = 45;
+const MASK: u64 = (1 << BITS) - 1;
+/// odd, so multiplying by it is invertible modulo 2^BITS
+const MULTIPLIER: u64 = 0x1E37_79B9_7F4B;
+const XOR: u64 = 0x0A5A_5A5A_5A5A;
+/// modulus of the checksum, which takes 10 bits
+const CHECKSUM: u64 = 1021;
+
+/// inverse of MULTIPLIER modulo 2^BITS, by Newton's iteration
+fn inverse() -> u64 {
+	let mut inv = MULTIPLIER;
+	for _ in 0..6 {
+		inv = inv.wrapping_mul(2u64.wrapping_sub(MULTIPLIER.wrapping_mul(inv)));
+	}
+	return inv & MASK;
+}
+
+impl Challenge {
+	pub fn new(
+		answer: Word,
+		difficulty: Difficulty,
+		max_guesses: u32,
+	) -> Result<Self, ChallengeError> {
+		if !(1..=15).contains(&max_guesses) {
+			return Err(ChallengeError::MaxGuesses(max_guesses));
+		}
+		return Ok(Self {
+			answer,
+			difficulty,
+			max_guesses,
+		});
+	}
+
+	/// layout: letters (5 bits each), word length - 1 (4 bits), difficulty (2 bits),
+	/// max guesses (4 bits), checksum (10 bits), then scrambled
+	pub fn encode(&self) -> String {
+		let mut payload = self
+			.answer
+			.iter()
+			.fold(0u64, |acc, &c| (acc << 5) | (c as u64 - 'A' as u64));
+		payload = (payload << 4) | (self.answer.len() as u64 - 1);
+		payload = (payload << 2)
+			| match self.difficulty {
+				Difficulty::Normal => 0,
+				Difficulty::Hard => 1,
+				Difficulty::Strict => 2,
+			};
+		payload = (payload << 4) | self.max_guesses as u64;
+		let value = (payload << 10) | (payload % CHECKSUM);
+		let mut code = ((value ^ XOR).wrapping_mul(MULTIPLIER)) & MASK;
+
+		let mut digits = [0u8; (BITS / 5) as usize];
+		for digit in digits.iter_mut().rev() {
+			*digit = ALPHABET[(code & 31) as usize];
+			code >>= 5;
+		}
+		return String::from_utf8(digits.to_vec()).unwrap();
+	}
+
+	/// case insensitive, dashes and spaces are ignored
+	pub fn decode(code: &str) -> Result<Self, ChallengeError> {
+		let digits: Vec<char> = code
+			.chars()
+			.filter(|c| *c != '-' && !c.is_whitespace())
+			.map(|c| c.to_ascii_uppercase())
+			.collect();
+		if digits.len() != (BITS / 5) as usize {
+			return Err(ChallengeError::Malformed);
+		}
+		let mut code = 0u64;
+		for c in digits {
+			let digit = ALPHABET
+				.iter()
+				.position(|&d| d as char == c)
+				.ok_or(ChallengeError::Malformed)?;
+			code = (code << 5) | digit as u64;
+		}
+		let value = (code.wrapping_mul(inverse()) & MASK) ^ XOR;
+		let mut payload = value >> 10;
+		if value & 1023 != payload % CHECKSUM {
+			return Err(ChallengeError::Malformed);
+		}
+
+		let max_guesses = (payload & 15) as u32;
+		payload >>= 4;
+		let difficulty = match payload & 3 {
+			0 => Difficulty::Normal,
+			1 => Difficulty::Hard,
+			2 => Difficulty::Strict,
+			_ => return Err(ChallengeError::Malformed),
+		};
+		payload >>= 2;
+		let len = (payload & 15) as u32 + 1;
+		if len != 5 {
+			return Err(ChallengeError::WordLength(len));
+		}
+		payload >>= 4;
+		let mut letters = ['A'; 5];
+		for c in letters.iter_mut().rev() {
+			*c = (b'A' + (payload & 31) as u8) as char;
+			payload >>= 5;
+		}
+		let answer = Word::new(letters).map_err(|_| ChallengeError::Malformed)?;
+		return Self::new(answer, difficulty, max_guesses);
+	}
+
+	/// ensure the answer is acceptable
+	pub fn check(&self, set_acceptable: &HashSet<Word>) -> Result<(), ChallengeError> {
+		match set_acceptable.contains(&self.answer) {
+			true => Ok(()),
+			false => Err(ChallengeError::NotAcceptable(self.answer)),
+		}
+	}
+}
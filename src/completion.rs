@@ -0,0 +1,60 @@
+use {
+	clap_complete::{generate, Shell},
+	std::io::Write,
+};
+
+/// asks `{bin} __complete` for answers and profiles, then falls back to the generated `_{bin}`
+const BASH: &str = r#"
+# answers and profiles are completed by {bin} itself
+_{bin}_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "${prev}" in
+        -w|--word)
+            COMPREPLY=($(compgen -W "$({bin} __complete word "${cur}" 2>/dev/null)" -- "${cur}"))
+            ;;
+        -p|--profile)
+            COMPREPLY=($(compgen -W "$({bin} __complete profile 2>/dev/null)" -- "${cur}"))
+            ;;
+        *)
+            _{bin} "$@"
+            ;;
+    esac
+}
+complete -F _{bin}_dynamic -o nosort -o bashdefault -o default {bin}
+"#;
+
+const ZSH: &str = r#"
+# answers and profiles are completed by {bin} itself
+_{bin}_dynamic() {
+    case "${words[CURRENT-1]}" in
+        (-w|--word) compadd -- ${(f)"$({bin} __complete word "${PREFIX}" 2>/dev/null)"} ;;
+        (-p|--profile) compadd -- ${(f)"$({bin} __complete profile 2>/dev/null)"} ;;
+        (*) _{bin} "$@" ;;
+    esac
+}
+compdef _{bin}_dynamic {bin}
+"#;
+
+const FISH: &str = r#"
+# answers and profiles are completed by {bin} itself
+complete -c {bin} -s w -l word -x -a "({bin} __complete word (commandline -ct) 2>/dev/null)"
+complete -c {bin} -s p -l profile -x -a "({bin} __complete profile 2>/dev/null)"
+"#;
+
+/// completion script of `shell` generated from `cmd`
+/// bash, zsh and fish also complete --word and --profile values by calling `__complete`
+pub fn write_completions(
+	shell: Shell,
+	cmd: &mut clap::Command,
+	buf: &mut dyn Write,
+) -> std::io::Result<()> {
+	let bin = cmd.get_name().to_string();
+	generate(shell, cmd, &bin, buf);
+	let dynamic = match shell {
+		Shell::Bash => BASH,
+		Shell::Zsh => ZSH,
+		Shell::Fish => FISH,
+		_ => return Ok(()),
+	};
+	return buf.write_all(dynamic.replace("{bin}", &bin).as_bytes());
+}
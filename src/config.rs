@@ -1,40 +1,54 @@
 use {
-	crate::{builtin_words, plate::*},
-	anyhow::{anyhow, Result},
-	clap::Parser,
-	serde::Deserialize,
-	serde_json::from_str,
-	std::{collections::HashSet, io::BufRead, str::from_utf8},
+	crate::{
+		archive, builtin_words,
+		challenge::{Challenge, ChallengeError},
+		plate::*,
+	},
+	clap::{
+		error::ErrorKind,
+		parser::{ArgMatches, ValueSource},
+		CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum,
+	},
+	clap_complete::Shell,
+	serde::{Deserialize, Serialize},
+	serde_json::{from_str, from_value, to_value, Map, Value},
+	std::{
+		collections::{BTreeMap, BTreeSet, HashSet},
+		fmt,
+		path::{Path, PathBuf},
+		time::Duration,
+	},
 };
 
-#[derive(Default, Deserialize, Parser, Debug)]
+// arguments, playing by default, doc comments here would become the about text
+#[derive(Default, Deserialize, Serialize, Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[serde(default)]
 struct Args {
-	/// select answer mode, conflict to -r
-	#[arg(short, long)]
-	word: Option<String>,
-
-	/// random mode, conflict to -s
-	#[arg(short, long, default_value_t = false)]
-	random: bool,
-
-	/// random seed, requires -r
-	#[arg(short, long)]
-	seed: Option<u64>,
-	/// random date, requires -r
-	#[arg(short, long, value_name = "DAY")]
-	day:  Option<u32>,
+	#[command(flatten)]
+	#[serde(flatten)]
+	files: FileArgs,
 
-	/// difficult mode
-	#[arg(short = 'D', long, default_value_t = false)]
-	difficult: bool,
+	#[command(flatten)]
+	#[serde(flatten)]
+	play: PlayArgs,
 
-	/// print statistic
-	#[arg(short = 't', long, default_value_t = false)]
-	stats: bool,
+	#[command(subcommand)]
+	#[serde(skip)]
+	command: Option<Command>,
+}
 
+/// Files used by every command
+#[derive(Default, Deserialize, Serialize, clap::Args, Debug)]
+#[serde(default)]
+struct FileArgs {
 	/// final word set
-	#[arg(short = 'f', long = "final-set", value_name = "FINAL_SET_FILE")]
+	#[arg(
+		short = 'f',
+		long = "final-set",
+		value_name = "FINAL_SET_FILE",
+		global = true
+	)]
 	#[serde(rename = "final_set")]
 	final_set_src: Option<String>,
 
@@ -42,20 +56,261 @@ struct Args {
 	#[arg(
 		short = 'a',
 		long = "acceptable-set",
-		value_name = "ACCEPTABLE_SET_FILE"
+		value_name = "ACCEPTABLE_SET_FILE",
+		global = true
 	)]
 	#[serde(rename = "acceptable_set")]
 	acceptable_set_src: Option<String>,
 
 	/// state json file
-	#[arg(short = 'S', long = "state", value_name = "STATE_FILE")]
+	#[arg(short = 'S', long = "state", value_name = "STATE_FILE", global = true)]
 	#[serde(rename = "state")]
 	state_src: Option<String>,
 
-	/// default config file
-	#[arg(short, long = "config", value_name = "CONFIG_FILE")]
+	/// default config file, JSON or TOML by extension [default: $XDG_CONFIG_HOME/wordle/config.toml]
+	#[arg(short, long = "config", value_name = "CONFIG_FILE", global = true)]
 	#[serde(rename = "config")]
 	config_src: Option<String>,
+
+	/// use a [profile.NAME] table of the config file
+	#[arg(short, long, value_name = "NAME", global = true)]
+	#[serde(skip)]
+	profile: Option<String>,
+}
+
+/// Options of a game, also allowed before any subcommand
+#[derive(Default, Deserialize, Serialize, clap::Args, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct PlayArgs {
+	/// select answer mode
+	#[arg(short, long, conflicts_with_all = ["random", "seed", "day"])]
+	word: Option<String>,
+
+	/// random mode
+	#[arg(short, long, default_value_t = false)]
+	random: bool,
+
+	/// random seed
+	#[arg(short, long)]
+	seed: Option<u64>,
+	/// random date
+	#[arg(short, long, value_name = "DAY")]
+	day:  Option<u32>,
+
+	/// play a challenge code
+	#[arg(long, value_name = "CODE", conflicts_with_all = ["word", "random", "archive", "race", "time_attack"])]
+	challenge: Option<String>,
+
+	/// replay a past daily puzzle of the seed, up to -d or today
+	#[arg(long, default_value_t = false, conflicts_with_all = ["word", "race", "players", "time_attack"])]
+	archive: bool,
+
+	/// join a head-to-head race at a ws:// URL
+	#[arg(long, value_name = "URL", conflicts_with_all = ["word", "random", "seed", "day", "players"])]
+	race: Option<String>,
+
+	/// racer name, defaults to $USER
+	#[arg(long)]
+	name: Option<String>,
+
+	/// hot-seat mode, players take turns in this order
+	#[arg(long, value_delimiter = ',', value_name = "NAME,...")]
+	players: Option<Vec<String>>,
+
+	/// each player of a hot-seat round gets the next answer
+	#[arg(long, default_value_t = false, requires = "players")]
+	alternate: bool,
+
+	/// difficult mode
+	#[arg(short = 'D', long, default_value_t = false)]
+	difficult: bool,
+
+	/// strict difficult mode, every known constraint must hold
+	#[arg(long, default_value_t = false)]
+	strict: bool,
+
+	/// print statistic
+	#[arg(short = 't', long, default_value_t = false)]
+	stats: bool,
+
+	/// record the time taken by each guess
+	#[arg(long, default_value_t = false)]
+	timed: bool,
+
+	/// seconds allowed for each guess, the game is lost by a guess entered later
+	/// the prompt itself does not time out
+	#[arg(long, value_name = "SECS")]
+	countdown: Option<u64>,
+
+	/// solve as many random answers as possible in this many minutes
+	#[arg(long, value_name = "MINUTES", conflicts_with_all = ["word", "race", "players"])]
+	time_attack: Option<u64>,
+
+	/// analyze guesses after each game
+	#[arg(long, default_value_t = false)]
+	analyze: bool,
+
+	/// unlock achievements and keep them in the state file
+	#[arg(long, default_value_t = false)]
+	achievements: bool,
+}
+
+/// Subcommands, playing is the default
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+pub enum Command {
+	/// play games, the same as no subcommand
+	Play(PlayArgs),
+	/// play the daily puzzle of today
+	Daily(PlayArgs),
+	/// print the statistic of the state file
+	Stats {
+		/// player of a hot-seat state file
+		#[arg(long)]
+		player: Option<String>,
+	},
+	/// let the solver play an answer
+	Solve {
+		/// answer, must be acceptable
+		word:      String,
+		/// difficult mode
+		#[arg(short = 'D', long, default_value_t = false)]
+		difficult: bool,
+		/// strict difficult mode
+		#[arg(long, default_value_t = false)]
+		strict:    bool,
+	},
+	/// suggest guesses for a puzzle played elsewhere
+	/// type each guess and its feedback, e.g. `crane GYXXG`, or `undo`
+	Assist,
+	/// check the word lists and print their sizes
+	Lists {
+		/// print the final list instead
+		#[arg(long = "final", default_value_t = false, conflicts_with = "acceptable")]
+		print_final: bool,
+		/// print the acceptable list instead
+		#[arg(long, default_value_t = false)]
+		acceptable:  bool,
+	},
+	/// build the cache of feedback of every acceptable guess against every final answer
+	/// it is rebuilt when the word lists change, solve, assist and --analyze use it when present
+	Matrix {
+		/// cache file, $XDG_CACHE_HOME/wordle/patterns.bin by default
+		#[arg(long)]
+		cache: Option<String>,
+	},
+	/// rank users by the state files in a directory
+	Leaderboard {
+		/// directory of {user}.json state files
+		dir:  String,
+		/// random seed of the daily answer
+		#[arg(short, long)]
+		seed: Option<u64>,
+		/// random date of the daily answer, ranks its fastest solves
+		#[arg(short, long)]
+		day:  Option<u32>,
+		/// print JSON for the web frontend
+		#[arg(long, default_value_t = false)]
+		json: bool,
+	},
+	/// share custom puzzles as codes
+	Challenge {
+		#[command(subcommand)]
+		action: ChallengeCommand,
+	},
+	/// inspect the configuration
+	Config {
+		#[command(subcommand)]
+		action: ConfigCommand,
+	},
+	/// print a shell completion script, e.g. `source <(wordle completions bash)`
+	Completions { shell: Shell },
+	/// print the man page in roff
+	Manpage,
+	/// candidates of dynamic completion, called by completion scripts
+	#[command(name = "__complete", hide = true)]
+	Complete {
+		kind:   CompleteKind,
+		/// what has been typed so far
+		#[arg(default_value = "")]
+		prefix: String,
+	},
+}
+
+/// Values completed by the program itself rather than the completion script
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompleteKind {
+	/// acceptable words, for --word
+	Word,
+	/// profiles of the config file, for --profile
+	Profile,
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+pub enum ChallengeCommand {
+	/// print the code of a puzzle, to be played with --challenge
+	Create {
+		/// answer, must be acceptable
+		word:        String,
+		/// difficult mode
+		#[arg(short = 'D', long, default_value_t = false)]
+		difficult:   bool,
+		/// strict difficult mode
+		#[arg(long, default_value_t = false)]
+		strict:      bool,
+		/// guesses allowed
+		#[arg(long, default_value_t = MAX_GUESSES)]
+		max_guesses: u32,
+	},
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+pub enum ConfigCommand {
+	/// print the effective configuration and where each value comes from
+	Show,
+}
+
+/// A config file: settings, then profiles overriding them
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ConfigFile {
+	#[serde(flatten)]
+	args:    Args,
+	profile: BTreeMap<String, Args>,
+}
+
+/// Where a setting comes from, from the lowest priority
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+	Default,
+	/// path of the config file
+	File(String),
+	/// (name, path of the config file)
+	Profile(String, String),
+	/// name of the environment variable
+	Env(String),
+	/// command line arguments
+	Arg,
+}
+
+impl fmt::Display for Source {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Source::Default => write!(f, "default"),
+			Source::File(path) => write!(f, "config file {}", path),
+			Source::Profile(name, path) => write!(f, "profile {} of {}", name, path),
+			Source::Env(var) => write!(f, "environment variable {}", var),
+			Source::Arg => write!(f, "command line"),
+		}
+	}
+}
+
+/// A setting of the effective configuration, named as in config files
+#[derive(Clone, Debug, PartialEq)]
+pub struct Setting {
+	pub key:    String,
+	/// null if not set
+	pub value:  Value,
+	pub source: Source,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -66,73 +321,531 @@ pub enum WordSrc {
 	Select(Word),
 	/// random(seed, start_date)
 	Random(u64, u32),
+	/// shared random answer of a race server
+	Race(String),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Config {
-	pub difficult:      bool,
+	pub difficulty:     Difficulty,
+	/// guesses allowed in a game
+	pub max_guesses:    u32,
 	pub stats:          bool,
+	pub analyze:        bool,
+	/// unlock achievements after each game
+	pub achievements:   bool,
 	pub word_src:       WordSrc,
 	pub set_acceptable: HashSet<Word>,
 	pub set_final:      HashSet<Word>,
 	pub list_final:     Vec<Word>,
 	pub state_src:      Option<String>,
+	/// pick the answer among past daily puzzles
+	pub archive:        bool,
+	/// racer name
+	pub name:           String,
+	/// hot-seat players, empty for a single player
+	pub players:        Vec<String>,
+	/// hot-seat players get answers in turn instead of sharing one
+	pub alternate:      bool,
+	/// record the time taken by each guess
+	pub timed:          bool,
+	/// time allowed for each guess
+	pub countdown:      Option<Duration>,
+	/// length of a time attack
+	pub time_attack:    Option<Duration>,
+	pub command:        Option<Command>,
 }
 
-pub fn config() -> Result<Config> {
-	let args0 = Args::parse();
-	let args1: Args = match args0.config_src {
-		None => Default::default(),
-		Some(path) => from_str(&std::fs::read_to_string(&path)?)?,
-	};
-	let args = Args {
-		word:               args0.word.or(args1.word),
-		random:             args0.random || args1.random,
-		seed:               args0.seed.or(args1.seed),
-		day:                args0.day.or(args1.day),
-		difficult:          args0.difficult || args1.difficult,
-		stats:              args0.stats || args1.stats,
-		final_set_src:      args0.final_set_src.or(args1.final_set_src),
-		acceptable_set_src: args0.acceptable_set_src.or(args1.acceptable_set_src),
-		state_src:          args0.state_src.or(args1.state_src),
-		config_src:         None,
-	};
+/// Reasons for rejecting a configuration
+#[derive(Debug)]
+pub enum ConfigError {
+	/// two arguments that cannot be used together
+	Conflict(&'static str, &'static str),
+	/// (path, error): file cannot be read
+	Io(String, std::io::Error),
+	/// (path, error): config file is not valid
+	Json(String, serde_json::Error),
+	/// (path, error): TOML config file is not valid
+	Toml(String, toml::de::Error),
+	/// (variable, error): environment variable is not valid
+	Env(String, serde_json::Error),
+	/// environment variable is not valid unicode
+	EnvUnicode(String),
+	/// profile not found in the config file
+	UnknownProfile(String),
+	/// (list, line, error): word list contains a non-word
+	InvalidWord(String, usize, WordError),
+	/// word is in the final list but not in the acceptable list
+	NotSubset(Word),
+	/// selected answer is not a word
+	InvalidSelect(String, WordError),
+	/// (code, error): challenge code cannot be played
+	InvalidChallenge(String, ChallengeError),
+}
+
+impl fmt::Display for ConfigError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ConfigError::Conflict(a, b) => write!(f, "{} cannot be used with {}", a, b),
+			ConfigError::Io(path, e) => write!(f, "cannot read {}: {}", path, e),
+			ConfigError::Json(path, e) => write!(f, "invalid config file {}: {}", path, e),
+			ConfigError::Toml(path, e) => write!(f, "invalid config file {}: {}", path, e),
+			ConfigError::Env(var, e) => write!(f, "invalid environment variable {}: {}", var, e),
+			ConfigError::EnvUnicode(var) => {
+				write!(f, "environment variable {} is not valid unicode", var)
+			}
+			ConfigError::UnknownProfile(name) => write!(f, "profile {} not found", name),
+			ConfigError::InvalidWord(list, line, e) => write!(f, "{}:{}: {}", list, line, e),
+			ConfigError::NotSubset(word) => {
+				write!(
+					f,
+					"{} is in the final list but not in the acceptable list",
+					word
+				)
+			}
+			ConfigError::InvalidSelect(input, e) => write!(f, "invalid answer {:?}: {}", input, e),
+			ConfigError::InvalidChallenge(code, e) => {
+				write!(f, "invalid challenge {}: {}", code, e)
+			}
+		}
+	}
+}
+
+impl std::error::Error for ConfigError {}
+
+/// ensure every word of `list_final` is acceptable
+pub fn check_subset(
+	list_final: &[Word],
+	set_acceptable: &HashSet<Word>,
+) -> Result<(), ConfigError> {
+	match list_final.iter().find(|w| !set_acceptable.contains(w)) {
+		None => Ok(()),
+		Some(word) => Err(ConfigError::NotSubset(*word)),
+	}
+}
+
+fn read_file(path: &str) -> Result<String, ConfigError> {
+	std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_string(), e))
+}
+
+/// parse a word list, one word per line
+pub fn parse_list<T: FromIterator<Word>>(name: &str, list: &str) -> Result<T, ConfigError> {
+	list.lines()
+		.enumerate()
+		.map(|(line, s)| {
+			word_from_str(s.trim())
+				.map_err(|e| ConfigError::InvalidWord(name.to_string(), line + 1, e))
+		})
+		.collect()
+}
 
+/// load acceptable set and final list from files, or builtin lists if None
+pub fn load_lists(
+	acceptable_set_src: Option<String>,
+	final_set_src: Option<String>,
+) -> Result<(HashSet<Word>, Vec<Word>), ConfigError> {
 	let parse_builtin_list =
 		|list: &[&str]| list.iter().map(|&s| word_from_str(s).unwrap()).collect();
-	let read_list_src = |list_src| -> Result<Vec<Word>> {
-		std::io::BufReader::new(std::fs::File::open(list_src)?)
-			.split(b'\n')
-			.map(|r| -> Result<Word> { Ok(word_from_str(from_utf8(&r?)?)?) })
-			.collect()
-	};
-	let list_acceptable: Vec<Word> = match args.acceptable_set_src {
+	let read_list_src = |list_src: String| parse_list(&list_src, &read_file(&list_src)?);
+	let list_acceptable: Vec<Word> = match acceptable_set_src {
 		None => parse_builtin_list(builtin_words::ACCEPTABLE),
 		Some(src) => read_list_src(src)?,
 	};
-	let list_final: Vec<Word> = match args.final_set_src {
+	let list_final: Vec<Word> = match final_set_src {
 		None => parse_builtin_list(builtin_words::FINAL),
 		Some(src) => read_list_src(src)?,
 	};
 	let set_acceptable: HashSet<Word> = list_acceptable.into_iter().collect();
-	if !list_final.iter().all(|s| set_acceptable.contains(s)) {
-		return Err(anyhow!("list_fianl is not subset of list_acceptable"));
+	check_subset(&list_final, &set_acceptable)?;
+	return Ok((set_acceptable, list_final));
+}
+
+/// prefix of environment variables overriding config files, e.g. WORDLE_DIFFICULT=true
+const ENV_PREFIX: &str = "WORDLE_";
+
+/// $XDG_CONFIG_HOME/wordle/config.toml, or config.json if only that exists
+fn default_config_path() -> Option<PathBuf> {
+	let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+		Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+		_ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+	};
+	return ["config.toml", "config.json"]
+		.into_iter()
+		.map(|name| dir.join("wordle").join(name))
+		.find(|path| path.exists());
+}
+
+/// the config file and its raw content, which tells the settings actually written
+fn read_config_file(path: &str) -> Result<(ConfigFile, Value), ConfigError> {
+	let content = read_file(path)?;
+	return match Path::new(path).extension().and_then(|e| e.to_str()) {
+		Some("toml") => {
+			let parse = |content| -> Result<(ConfigFile, Value), toml::de::Error> {
+				Ok((toml::from_str(content)?, toml::from_str(content)?))
+			};
+			parse(&content).map_err(|e| ConfigError::Toml(path.to_string(), e))
+		}
+		_ => {
+			let parse = |content| -> Result<(ConfigFile, Value), serde_json::Error> {
+				Ok((from_str(content)?, from_str(content)?))
+			};
+			parse(&content).map_err(|e| ConfigError::Json(path.to_string(), e))
+		}
+	};
+}
+
+/// settings of a config source, by name
+type Layer = Map<String, Value>;
+
+/// settings of `args` named in `given`, an explicit false is kept
+fn layer<T: Serialize>(args: &T, given: &BTreeSet<String>) -> Layer {
+	let mut result = match to_value(args) {
+		Ok(Value::Object(map)) => map,
+		_ => unreachable!(),
+	};
+	result.retain(|key, value| key != "config" && !value.is_null() && given.contains(key));
+	return result;
+}
+
+/// names of the settings written in a raw config table
+fn keys(raw: Option<&Value>) -> BTreeSet<String> {
+	match raw {
+		Some(Value::Object(map)) => map.keys().cloned().collect(),
+		_ => BTreeSet::new(),
+	}
+}
+
+/// settings given on the command line in `matches` of `command`
+/// options are named in config files by their long names, so defaults of clap are left out
+fn given_keys(command: &clap::Command, matches: &ArgMatches) -> BTreeSet<String> {
+	command
+		.get_arguments()
+		.filter(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+		.filter_map(|arg| arg.get_long())
+		.map(|long| long.replace('-', "_"))
+		.collect()
+}
+
+/// settings of WORDLE_* variables, a value is taken as JSON, a string or a comma separated list
+fn env_layer() -> Result<Vec<(String, Layer)>, ConfigError> {
+	let mut result = vec![];
+	let known = layer_keys();
+	// other variables are not ours to reject, even if they are not unicode
+	for (var, raw) in std::env::vars_os() {
+		let var = match var.into_string() {
+			Ok(var) => var,
+			Err(_) => continue,
+		};
+		let key = match var.strip_prefix(ENV_PREFIX) {
+			Some(key) => key.to_lowercase(),
+			None => continue,
+		};
+		if !known.contains(&key) {
+			continue;
+		}
+		let raw = raw
+			.into_string()
+			.map_err(|_| ConfigError::EnvUnicode(var.clone()))?;
+		let candidates = [
+			from_str(&raw).ok(),
+			Some(Value::String(raw.clone())),
+			Some(Value::Array(
+				raw.split(',')
+					.map(|s| Value::String(s.to_string()))
+					.collect(),
+			)),
+		];
+		let mut error = None;
+		for value in candidates.into_iter().flatten() {
+			let map = Map::from_iter([(key.clone(), value)]);
+			match from_value::<Args>(Value::Object(map)) {
+				Ok(args) => {
+					result.push((var.clone(), layer(&args, &BTreeSet::from([key.clone()]))));
+					error = None;
+					break;
+				}
+				Err(e) => error = error.or(Some(e)),
+			}
+		}
+		if let Some(e) = error {
+			return Err(ConfigError::Env(var, e));
+		}
+	}
+	result.sort_by(|a, b| a.0.cmp(&b.0));
+	return Ok(result);
+}
+
+/// names of all settings in config files
+fn layer_keys() -> Vec<String> {
+	match to_value(Args::default()) {
+		Ok(Value::Object(map)) => map
+			.into_iter()
+			.map(|(key, _)| key)
+			.filter(|key| key != "config")
+			.collect(),
+		_ => unreachable!(),
 	}
+}
+
+/// config file given by `files`, WORDLE_CONFIG or at the default location
+fn config_path(files: &FileArgs) -> Option<String> {
+	files
+		.config_src
+		.clone()
+		.or(std::env::var(format!("{}CONFIG", ENV_PREFIX)).ok())
+		.or(default_config_path().map(|p| p.to_string_lossy().to_string()))
+}
+
+/// merge config file, profile, environment variables and `given` settings of `cli`
+/// the later the higher priority
+fn merge_args((cli, given): (Args, BTreeSet<String>)) -> Result<(Args, Vec<Setting>), ConfigError> {
+	let path = config_path(&cli.files);
+	let profile = cli
+		.files
+		.profile
+		.clone()
+		.or(std::env::var(format!("{}PROFILE", ENV_PREFIX)).ok());
+
+	let mut layers: Vec<(Source, Layer)> = vec![];
+	let (mut file, raw) = match &path {
+		None => (ConfigFile::default(), Value::Null),
+		Some(path) => read_config_file(path)?,
+	};
+	let path = path.unwrap_or_default();
+	layers.push((
+		Source::File(path.clone()),
+		layer(&file.args, &keys(Some(&raw))),
+	));
+	if let Some(name) = profile {
+		let args = file
+			.profile
+			.remove(&name)
+			.ok_or(ConfigError::UnknownProfile(name.clone()))?;
+		let written = keys(raw.get("profile").and_then(|p| p.get(&name)));
+		layers.push((Source::Profile(name, path), layer(&args, &written)));
+	}
+	for (var, map) in env_layer()? {
+		layers.push((Source::Env(var), map));
+	}
+	layers.push((Source::Arg, layer(&cli, &given)));
+
+	let mut merged = Map::new();
+	let mut sources = BTreeMap::new();
+	for (source, map) in layers {
+		for (key, value) in map {
+			sources.insert(key.clone(), source.clone());
+			merged.insert(key, value);
+		}
+	}
+	let defaults = to_value(Args::default()).unwrap();
+	let settings = layer_keys()
+		.into_iter()
+		.map(|key| Setting {
+			value: merged.get(&key).unwrap_or(&defaults[&key]).clone(),
+			source: sources.get(&key).cloned().unwrap_or(Source::Default),
+			key,
+		})
+		.collect();
+	let args = Args {
+		command: cli.command,
+		..from_value(Value::Object(merged)).unwrap()
+	};
+	return Ok((args, settings));
+}
+
+/// command line arguments, with options of `play` and `daily` moved to the top level
+/// and the names of the settings given in them
+fn parse_args() -> (Args, BTreeSet<String>) {
+	let command = Args::command();
+	let matches = command.clone().get_matches();
+	let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+	let mut given = given_keys(&command, &matches);
+	let top = layer(&args.play, &given);
+	let sub_given = match matches.subcommand() {
+		Some((name @ ("play" | "daily"), sub_matches)) => {
+			given_keys(command.find_subcommand(name).unwrap(), sub_matches)
+		}
+		_ => BTreeSet::new(),
+	};
+	// options before and after `play` are both taken
+	let merge = |play: &PlayArgs| -> PlayArgs {
+		let mut merged = top.clone();
+		merged.extend(layer(play, &sub_given));
+		return from_value(Value::Object(merged)).unwrap();
+	};
+	match args.command.take() {
+		None => {}
+		Some(Command::Play(play)) => args.play = merge(&play),
+		Some(Command::Daily(play)) => {
+			args.play = merge(&play);
+			args.command = Some(Command::Daily(play));
+		}
+		// game options are shown by `config show` as they would be played
+		command @ Some(Command::Config {
+			..
+		}) => args.command = command,
+		Some(_) if !top.is_empty() => {
+			let name = matches.subcommand_name().unwrap_or_default();
+			let keys: Vec<&String> = top.keys().collect();
+			Args::command()
+				.error(
+					ErrorKind::ArgumentConflict,
+					format!("game options {:?} cannot be used with '{}'", keys, name),
+				)
+				.exit();
+		}
+		command => args.command = command,
+	}
+	given.extend(sub_given);
+	return (args, given);
+}
 
-	let word_src: WordSrc = match (args.word, args.random, args.seed, args.day) {
-		(None, false, _, _) => WordSrc::Ask,
-		(None, true, seed, date) => WordSrc::Random(seed.unwrap_or(0), date.unwrap_or(1)),
-		(Some(word_str), false, None, None) => WordSrc::Select(word_from_str(word_str.as_str())?),
-		_ => Err(anyhow!("arguments conflict!"))?,
+/// definition of the command line, for completions and the man page
+pub fn cli() -> clap::Command {
+	Args::command()
+}
+
+/// names of the profiles in the config file
+pub fn profiles() -> Result<Vec<String>, ConfigError> {
+	let file = match config_path(&parse_args().0.files) {
+		None => ConfigFile::default(),
+		Some(path) => read_config_file(&path)?.0,
+	};
+	return Ok(file.profile.into_keys().collect());
+}
+
+/// every setting of the effective configuration, ordered by name
+pub fn settings() -> Result<Vec<Setting>, ConfigError> {
+	return Ok(merge_args(parse_args())?.1);
+}
+
+pub fn config() -> Result<Config, ConfigError> {
+	let (
+		Args {
+			files,
+			play: args,
+			command,
+		},
+		_,
+	) = merge_args(parse_args())?;
+
+	let (set_acceptable, list_final) = load_lists(files.acceptable_set_src, files.final_set_src)?;
+
+	// clap rejects conflicting arguments, conflicts with config files are found here
+	let players = args.players.unwrap_or_default();
+	match (&args.race, players.is_empty(), args.alternate) {
+		(Some(_), false, _) => Err(ConfigError::Conflict("--race", "--players"))?,
+		(_, true, true) => Err(ConfigError::Conflict("--alternate", "single player mode"))?,
+		_ => {}
+	}
+	if args.time_attack.is_some() {
+		match (&args.word, &args.race, players.is_empty()) {
+			(Some(_), _, _) => Err(ConfigError::Conflict("--time-attack", "--word"))?,
+			(None, Some(_), _) => Err(ConfigError::Conflict("--time-attack", "--race"))?,
+			(None, None, false) => Err(ConfigError::Conflict("--time-attack", "--players"))?,
+			(None, None, true) => {}
+		}
+	}
+	if args.challenge.is_some() {
+		match (
+			&args.word,
+			&args.race,
+			args.random || args.archive,
+			args.time_attack,
+		) {
+			(Some(_), _, _, _) => Err(ConfigError::Conflict("--challenge", "--word"))?,
+			(None, Some(_), _, _) => Err(ConfigError::Conflict("--challenge", "--race"))?,
+			(None, None, true, _) => Err(ConfigError::Conflict("--challenge", "random mode"))?,
+			(None, None, false, Some(_)) => {
+				Err(ConfigError::Conflict("--challenge", "--time-attack"))?
+			}
+			(None, None, false, None) => {}
+		}
+	}
+	let challenge = match args.challenge {
+		None => None,
+		Some(code) => Some(
+			Challenge::decode(&code)
+				.and_then(|c| c.check(&set_acceptable).map(|_| c))
+				.map_err(|e| ConfigError::InvalidChallenge(code, e))?,
+		),
+	};
+	if args.archive {
+		match (&args.word, &args.race, players.is_empty(), args.time_attack) {
+			(Some(_), _, _, _) => Err(ConfigError::Conflict("--archive", "--word"))?,
+			(None, Some(_), _, _) => Err(ConfigError::Conflict("--archive", "--race"))?,
+			(None, None, false, _) => Err(ConfigError::Conflict("--archive", "--players"))?,
+			(None, None, true, Some(_)) => {
+				Err(ConfigError::Conflict("--archive", "--time-attack"))?
+			}
+			(None, None, true, None) => {}
+		}
+	}
+	let daily = matches!(command, Some(Command::Daily(_)));
+	if daily {
+		match (&args.word, &args.race, &challenge, args.archive) {
+			(Some(_), _, _, _) => Err(ConfigError::Conflict("daily", "--word"))?,
+			(None, Some(_), _, _) => Err(ConfigError::Conflict("daily", "--race"))?,
+			(None, None, Some(_), _) => Err(ConfigError::Conflict("daily", "--challenge"))?,
+			(None, None, None, true) => Err(ConfigError::Conflict("daily", "--archive"))?,
+			(None, None, None, false) => {}
+		}
+	}
+	// time attack, archive and daily take answers from rand_words
+	let random = args.random || args.time_attack.is_some() || args.archive || daily;
+	// the archive and daily go up to today by default
+	let day = match args.archive || daily {
+		true => Some(args.day.unwrap_or_else(archive::today)),
+		false => args.day,
+	};
+
+	let word_src: WordSrc = match (args.race, args.word, random, args.seed, day) {
+		(Some(url), None, false, None, None) => WordSrc::Race(url),
+		(Some(_), Some(_), _, _, _) => Err(ConfigError::Conflict("--race", "--word"))?,
+		(Some(_), None, true, _, _) => Err(ConfigError::Conflict("--race", "--random"))?,
+		(Some(_), None, false, Some(_), _) => Err(ConfigError::Conflict("--race", "--seed"))?,
+		(Some(_), None, false, None, Some(_)) => Err(ConfigError::Conflict("--race", "--day"))?,
+		(None, None, false, _, _) => WordSrc::Ask,
+		(None, None, true, seed, date) => WordSrc::Random(seed.unwrap_or(0), date.unwrap_or(1)),
+		(None, Some(word_str), false, None, None) => WordSrc::Select(
+			word_from_str(&word_str).map_err(|e| ConfigError::InvalidSelect(word_str, e))?,
+		),
+		(None, Some(_), true, _, _) => Err(ConfigError::Conflict("--word", "--random"))?,
+		(None, Some(_), false, Some(_), _) => Err(ConfigError::Conflict("--word", "--seed"))?,
+		(None, Some(_), false, None, Some(_)) => Err(ConfigError::Conflict("--word", "--day"))?,
+	};
+	let (word_src, difficulty, max_guesses) = match challenge {
+		None => (
+			word_src,
+			Difficulty::from_flags(args.difficult, args.strict),
+			MAX_GUESSES,
+		),
+		Some(c) => (WordSrc::Select(c.answer), c.difficulty, c.max_guesses),
 	};
 
 	return Ok(Config {
-		difficult: args.difficult,
+		difficulty,
+		max_guesses,
 		stats: args.stats,
+		analyze: args.analyze,
+		achievements: args.achievements,
 		word_src,
 		set_acceptable,
 		set_final: list_final.iter().cloned().collect(),
 		list_final,
-		state_src: args.state_src,
+		state_src: files.state_src,
+		archive: args.archive,
+		name: args
+			.name
+			.or(std::env::var("USER").ok())
+			.unwrap_or("player".to_string()),
+		players,
+		alternate: args.alternate,
+		timed: args.timed || args.countdown.is_some() || args.time_attack.is_some(),
+		countdown: args.countdown.map(Duration::from_secs),
+		time_attack: args
+			.time_attack
+			.map(|minutes| Duration::from_secs(minutes * 60)),
+		command,
 	});
 }
@@ -1,21 +1,66 @@
 use {
-	crate::{plate::*, statistic::Statistic},
-	anyhow::{Error, Result},
+	crate::{
+		achievement::Achievement,
+		analysis::Analysis,
+		archive::ArchiveDay,
+		assist::Assistant,
+		leaderboard::Leaderboard,
+		plate::*,
+		race::Racer,
+		statistic::{PlayerStatistic, Statistic},
+	},
+	anyhow::{anyhow, Error, Result},
 };
 
 mod cmd;
 mod tty;
 pub use {cmd::Cmd, tty::Tty};
 
+/// players of a round ordered by result: winners by guesses, then the rest
+pub fn round_ranking(round: &[(String, Plate)]) -> Vec<&(String, Plate)> {
+	let mut result: Vec<&(String, Plate)> = round.iter().collect();
+	result.sort_by_key(|(_, plate)| (!plate.is_win(), plate.count()));
+	return result;
+}
+
 pub trait Interactor {
 	fn read_word(&self) -> Result<Word> {
 		let mut buf = String::new();
 		std::io::stdin().read_line(&mut buf)?;
-		return word_from_str(buf.trim());
+		let input = buf.trim();
+		return word_from_str(input).map_err(|e| GuessError::Invalid(input.to_string(), e).into());
+	}
+	/// read a day between 1 and `last`
+	fn read_day(&self, last: u32) -> Result<u32> {
+		let mut buf = String::new();
+		std::io::stdin().read_line(&mut buf)?;
+		let input = buf.trim();
+		return match input.parse::<u32>() {
+			Ok(day) if (1..=last).contains(&day) => Ok(day),
+			_ => Err(anyhow!("invalid day {:?}", input)),
+		};
 	}
 	fn new_round(&self) {}
+	/// hand over to `player` before a hot-seat game, instead of `new_round`
+	fn print_turn(&self, _player: &str) {
+		self.new_round();
+	}
 	fn print_guess(&self, _: &Plate);
-	fn print_result(&self, _: &Plate);
+	/// result of a game, with achievements it unlocked
+	fn print_result(&self, _: &Plate, _achievements: &[Achievement]);
 	fn print_statistic(&self, _: &Statistic);
+	/// answers solved and played before a time attack ran out
+	fn print_time_attack(&self, _solved: u64, _played: u64);
+	/// results of a hot-seat round, with totals of each player
+	fn print_scoreboard(&self, _round: &[(String, Plate)], _: &PlayerStatistic);
+	fn print_analysis(&self, _: &Analysis);
+	fn print_leaderboard(&self, _: &Leaderboard);
+	/// past daily puzzles to pick from, answers hidden
+	fn print_archive(&self, _: &[ArchiveDay]);
+	/// racers joined so far and racers needed to start
+	fn print_waiting(&self, _players: &[String], _needed: usize) {}
+	fn print_race(&self, _: &[Racer]);
+	/// candidates left in assist mode and the best next guesses
+	fn print_assist(&self, _: &Assistant, _suggestions: &[(Word, f64)]);
 	fn print_err(&self, _: Error);
 }
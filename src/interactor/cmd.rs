@@ -1,6 +1,9 @@
 use {
-	super::Interactor,
-	crate::{plate::*, statistic::*},
+	super::{round_ranking, Interactor},
+	crate::{
+		achievement::Achievement, analysis::Analysis, archive::*, assist::Assistant,
+		leaderboard::Leaderboard, plate::*, race::*, statistic::*,
+	},
 	anyhow::Error,
 };
 
@@ -12,15 +15,6 @@ impl Cmd {
 	}
 }
 
-fn state_to_char(state: &LetterState) -> char {
-	match state {
-		LetterState::Correct => 'G',
-		LetterState::Occured => 'Y',
-		LetterState::Redundant => 'R',
-		LetterState::Unknown => 'X',
-	}
-}
-
 impl Interactor for Cmd {
 	fn print_guess(&self, plate: &Plate) {
 		let state_fmt: String = plate
@@ -29,17 +23,17 @@ impl Interactor for Cmd {
 			.unwrap()
 			.1
 			.iter()
-			.map(state_to_char)
+			.map(LetterState::to_char)
 			.collect();
 		let keyboard_fmt: String = plate
 			.keyboard()
 			.as_arr()
 			.iter()
-			.map(state_to_char)
+			.map(LetterState::to_char)
 			.collect();
 		println!("{} {}", state_fmt, keyboard_fmt);
 	}
-	fn print_result(&self, plate: &Plate) {
+	fn print_result(&self, plate: &Plate, _: &[Achievement]) {
 		match plate.is_win() {
 			false => println!("FAILED {}", word_to_str(plate.goal())),
 			true => println!("CORRECT {}", plate.count()),
@@ -60,6 +54,85 @@ impl Interactor for Cmd {
 				.join(" ")
 		);
 	}
+	fn print_time_attack(&self, solved: u64, played: u64) {
+		println!("{} {}", solved, played);
+	}
+	fn print_scoreboard(&self, round: &[(String, Plate)], statistics: &PlayerStatistic) {
+		for (player, plate) in round_ranking(round) {
+			let s = statistics.get(player).unwrap();
+			let result = match plate.is_win() {
+				true => format!("CORRECT {}", plate.count()),
+				false => "FAILED".to_string(),
+			};
+			println!(
+				"{} {} {} {} {:.2}",
+				player,
+				result,
+				s.success_cnt(),
+				s.fail_cnt(),
+				s.success_attempt_average()
+			);
+		}
+	}
+	fn print_analysis(&self, a: &Analysis) {
+		for g in &a.guesses {
+			println!(
+				"{} {} {} {:.2} {} {:.2} {:.0} {:.2}",
+				g.guess,
+				g.before,
+				g.after,
+				g.expected,
+				g.best_guess,
+				g.best_expected,
+				g.skill,
+				g.luck
+			);
+		}
+		println!("{:.0} {:.2}", a.skill, a.luck);
+	}
+	fn print_leaderboard(&self, l: &Leaderboard) {
+		for (i, e) in l.entries.iter().enumerate() {
+			println!(
+				"{} {} {} {} {:.2} {:.2} {} {}",
+				i + 1,
+				e.user,
+				e.games,
+				e.wins,
+				e.win_rate,
+				e.average_attempts,
+				e.current_streak,
+				e.max_streak
+			);
+		}
+		if let Some(daily) = &l.daily {
+			println!("{}", daily);
+			for (i, solve) in l.solves.iter().enumerate() {
+				println!("{} {} {}", i + 1, solve.user, solve.guesses);
+			}
+		}
+	}
+	fn print_archive(&self, days: &[ArchiveDay]) {
+		for d in days {
+			let status = match d.status {
+				DayStatus::Won => "WON",
+				DayStatus::Played => "PLAYED",
+				DayStatus::Unplayed => "NEW",
+			};
+			println!("{} {}", d.day, status);
+		}
+	}
+	fn print_race(&self, players: &[Racer]) {
+		for (i, r) in ranking(players).into_iter().enumerate() {
+			match r.is_win() {
+				true => println!("{} {} CORRECT {}", i + 1, r.name, r.states.len()),
+				false => println!("{} {} FAILED", i + 1, r.name),
+			}
+		}
+	}
+	fn print_assist(&self, assistant: &Assistant, suggestions: &[(Word, f64)]) {
+		let words: Vec<String> = suggestions.iter().map(|(w, _)| word_to_str(w)).collect();
+		println!("{} {}", assistant.candidates().len(), words.join(" "));
+	}
 	fn print_err(&self, _: Error) {
 		println!("INVALID");
 	}
@@ -1,6 +1,9 @@
 use {
-	super::Interactor,
-	crate::{plate::*, statistic::*},
+	super::{round_ranking, Interactor},
+	crate::{
+		achievement::Achievement, analysis::Analysis, archive::*, assist::Assistant,
+		leaderboard::Leaderboard, plate::*, race::*, statistic::*,
+	},
 	anyhow::Error,
 	console::{style, StyledObject, Term},
 };
@@ -32,10 +35,41 @@ where
 	println!();
 }
 
+/// allowed letters of each position and known letter counts
+fn print_knowledge(plate: &Plate) {
+	let knowledge = plate.knowledge();
+	for i in 0..5usize {
+		print!("{}: ", i + 1);
+		println_iter(
+			knowledge
+				.allowed(i)
+				.map(|c| format_char((&c, &plate.keyboard()[c]))),
+		);
+	}
+	let counts: Vec<String> = ('A'..='Z')
+		.filter(|&c| knowledge.min_cnt(c) > 0)
+		.map(|c| match knowledge.max_cnt(c) {
+			Some(max) if max == knowledge.min_cnt(c) => format!("{}={}", c, max),
+			_ => format!("{}>={}", c, knowledge.min_cnt(c)),
+		})
+		.collect();
+	if !counts.is_empty() {
+		println!("counts: {}", counts.join(" "));
+	}
+	println!("---");
+}
+
 impl Interactor for Tty {
 	fn new_round(&self) {
 		Term::stdout().clear_screen().unwrap();
 	}
+	fn print_turn(&self, player: &str) {
+		println!("Pass to {} and press Enter", style(player).bold());
+		let mut buf = String::new();
+		let _ = std::io::stdin().read_line(&mut buf);
+		Term::stdout().clear_screen().unwrap();
+		println!("{}'s turn", style(player).bold());
+	}
 	fn print_guess(&self, plate: &Plate) {
 		Term::stdout().clear_screen().unwrap();
 		println!("---");
@@ -45,12 +79,27 @@ impl Interactor for Tty {
 		println!("---");
 		println_iter(('A'..='Z').map(|c| format_char((&c, &plate.keyboard()[c]))));
 		println!("---");
+		if !plate.durations().is_empty() {
+			println!("Time: {:.1}s", plate.duration().as_secs_f64());
+		}
+		print_knowledge(plate);
 	}
-	fn print_result(&self, plate: &Plate) {
+	fn print_result(&self, plate: &Plate, achievements: &[Achievement]) {
 		match plate.is_win() {
+			false if plate.is_time_up() => {
+				println!("{} {}", style("TIME UP").red(), word_to_str(plate.goal()))
+			}
 			false => println!("{} {}", style("FAILED").red(), word_to_str(plate.goal())),
 			true => println!("{} {}", style("CORRECT").green(), plate.count()),
 		}
+		for a in achievements {
+			println!(
+				"{} {} - {}",
+				style("Achievement unlocked:").yellow().bold(),
+				style(a.name()).bold(),
+				a.description()
+			);
+		}
 	}
 	fn print_statistic(&self, s: &Statistic) {
 		println!("Statistic:");
@@ -67,8 +116,225 @@ impl Interactor for Tty {
 				.collect::<Vec<String>>()
 				.join(" ")
 		);
+		if let Some(average) = s.average_time() {
+			println!(
+				"Time: average {:.1}s fastest {} per guess {:.1}s",
+				average.as_secs_f64(),
+				s.fastest_time()
+					.map_or("-".to_string(), |t| format!("{:.1}s", t.as_secs_f64())),
+				s.average_guess_time().unwrap_or_default().as_secs_f64()
+			);
+		}
+	}
+	fn print_time_attack(&self, solved: u64, played: u64) {
+		println!(
+			"{} {} solved in {} games",
+			style("Time attack:").bold(),
+			style(solved).green(),
+			played
+		);
+	}
+	fn print_scoreboard(&self, round: &[(String, Plate)], statistics: &PlayerStatistic) {
+		println!("Scoreboard:");
+		println!("player        this round  wins  fails  average");
+		for (player, plate) in round_ranking(round) {
+			let s = statistics.get(player).unwrap();
+			let result = match plate.is_win() {
+				true => style(format!("{:<10}", format!("CORRECT {}", plate.count()))).green(),
+				false => style(format!("{:<10}", "FAILED")).red(),
+			};
+			println!(
+				"{:<12}  {}  {:>4}  {:>5}  {:>7.2}",
+				player,
+				result,
+				s.success_cnt(),
+				s.fail_cnt(),
+				s.success_attempt_average()
+			);
+		}
+	}
+	fn print_analysis(&self, a: &Analysis) {
+		println!("Analysis:");
+		println!("guess  before  after  expected  best guess       skill  luck");
+		for g in &a.guesses {
+			println!(
+				"{}  {:>6}  {:>5}  {:>8.2}  {} {:>10.2}  {:>5.0}  {:+.2}",
+				g.guess,
+				g.before,
+				g.after,
+				g.expected,
+				g.best_guess,
+				g.best_expected,
+				g.skill,
+				g.luck
+			);
+		}
+		println!(
+			"skill: {} luck: {}",
+			style(format!("{:.0}", a.skill)).bold(),
+			style(format!("{:+.2}", a.luck)).bold()
+		);
+	}
+	fn print_leaderboard(&self, l: &Leaderboard) {
+		println!("Leaderboard:");
+		println!("rank  user          games  wins  win rate  average  streak  best");
+		for (i, e) in l.entries.iter().enumerate() {
+			println!(
+				"{:>4}  {:<12}  {:>5}  {:>4}  {:>7.1}%  {:>7.2}  {:>6}  {:>4}",
+				i + 1,
+				style(&e.user).bold(),
+				e.games,
+				e.wins,
+				e.win_rate * 100.0,
+				e.average_attempts,
+				e.current_streak,
+				e.max_streak
+			);
+		}
+		if let Some(daily) = &l.daily {
+			println!("Fastest solves of {}:", style(daily).green());
+			for (i, solve) in l.solves.iter().enumerate() {
+				println!("{:>4}  {:<12}  {}", i + 1, solve.user, solve.guesses);
+			}
+		}
+	}
+	fn print_archive(&self, days: &[ArchiveDay]) {
+		println!(
+			"Archive: {} won {} played",
+			style("■").green(),
+			style("■").yellow()
+		);
+		for row in days.chunks(10) {
+			let row: Vec<String> = row
+				.iter()
+				.map(|d| {
+					let day = style(format!("{:>4}", d.day));
+					match d.status {
+						DayStatus::Won => day.green(),
+						DayStatus::Played => day.yellow(),
+						DayStatus::Unplayed => day,
+					}
+					.to_string()
+				})
+				.collect();
+			println!("{}", row.join(" "));
+		}
+		println!("Pick a day (1-{}):", days.len());
+	}
+	fn print_waiting(&self, players: &[String], needed: usize) {
+		println!(
+			"waiting for racers: {} ({}/{})",
+			players.join(", "),
+			players.len(),
+			needed
+		);
+	}
+	fn print_race(&self, players: &[Racer]) {
+		println!("Race:");
+		for (i, r) in ranking(players).into_iter().enumerate() {
+			let place = match r.is_over() {
+				true => format!("{}.", i + 1),
+				false => "-".to_string(),
+			};
+			let blocks: Vec<String> = r
+				.states
+				.iter()
+				.map(|state| {
+					state
+						.chars()
+						.map(|c| match c {
+							'G' => style('■').green().to_string(),
+							'Y' => style('■').yellow().to_string(),
+							_ => style('■').red().to_string(),
+						})
+						.collect()
+				})
+				.collect();
+			let status = match (r.is_win(), r.is_over(), r.left) {
+				(true, _, _) => style("won".to_string()).green(),
+				(false, _, true) => style("left".to_string()).dim(),
+				(false, true, false) => style("failed".to_string()).red(),
+				(false, false, false) => style(format!("{}/{}", r.states.len(), MAX_GUESSES)),
+			};
+			println!(
+				"{:>3} {:<12} {} {}",
+				place,
+				r.name,
+				blocks.join(" "),
+				status
+			);
+		}
+	}
+	fn print_assist(&self, assistant: &Assistant, suggestions: &[(Word, f64)]) {
+		println!("---");
+		for (word, state) in assistant.history() {
+			println_iter(word.iter().zip(state.iter()).map(format_char));
+		}
+		println!("---");
+		let candidates = assistant.candidates();
+		match assistant.is_solved() {
+			true => println!("{}", style("SOLVED").green()),
+			false if candidates.len() <= 10 => println!(
+				"{} candidates: {}",
+				style(candidates.len()).bold(),
+				candidates
+					.iter()
+					.map(word_to_str)
+					.collect::<Vec<String>>()
+					.join(" ")
+			),
+			false => println!("{} candidates", style(candidates.len()).bold()),
+		}
+		if !assistant.is_solved() {
+			println!("guess  expected");
+			for (word, expected) in suggestions {
+				println!("{}  {:>8.2}", word, expected);
+			}
+		}
 	}
 	fn print_err(&self, e: Error) {
-		println!("{} {}", style("error:").red(), e);
+		let msg = match e.downcast_ref::<GuessError>() {
+			Some(GuessError::NotAcceptable(word)) => {
+				format!("{} is not in the word list", style(word).bold())
+			}
+			Some(&GuessError::MissingCorrect {
+				letter,
+				position,
+			}) => format!(
+				"{} must stay at position {}",
+				format_char((&letter, &LetterState::Correct)),
+				position + 1
+			),
+			Some(&GuessError::MissingOccured {
+				letter,
+			}) => format!(
+				"{} must be used in this guess",
+				format_char((&letter, &LetterState::Occured))
+			),
+			Some(&GuessError::Excluded {
+				letter,
+				position,
+			}) => format!(
+				"{} cannot be at position {}",
+				format_char((&letter, &LetterState::Occured)),
+				position + 1
+			),
+			Some(&GuessError::Absent {
+				letter,
+			}) => format!(
+				"{} is not in the answer",
+				format_char((&letter, &LetterState::Redundant))
+			),
+			Some(&GuessError::TooMany {
+				letter,
+				max,
+			}) => format!(
+				"{} occurs at most {} time(s)",
+				format_char((&letter, &LetterState::Redundant)),
+				max
+			),
+			_ => e.to_string(),
+		};
+		println!("{} {}", style("error:").red(), msg);
 	}
 }
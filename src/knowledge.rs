@@ -0,0 +1,173 @@
+use {
+	crate::{plate::*, util::LetterMap},
+	LetterState::*,
+};
+
+/// count bound meaning "no upper limit"
+const UNBOUNDED: u32 = 5;
+
+/// Constraints on the answer deduced from guess history
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Knowledge {
+	/// letters that may still appear at each position
+	allowed: [LetterMap<bool>; 5],
+	/// letters known Correct at each position
+	fixed:   [Option<Letter>; 5],
+	min_cnt: LetterMap<u32>,
+	max_cnt: LetterMap<u32>,
+}
+
+impl Default for Knowledge {
+	fn default() -> Self {
+		Self {
+			allowed: [LetterMap::filled(true); 5],
+			fixed:   [None; 5],
+			min_cnt: LetterMap::filled(0),
+			max_cnt: LetterMap::filled(UNBOUNDED),
+		}
+	}
+}
+
+impl Knowledge {
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	pub fn from_history(history: &[(Word, WordState)]) -> Self {
+		let mut result = Self::new();
+		for (word, state) in history {
+			result.add(word, state);
+		}
+		return result;
+	}
+
+	/// update knowledge by one guess and its feedback
+	pub fn add(&mut self, word: &Word, state: &WordState) {
+		let mut known_cnt: LetterMap<u32> = Default::default();
+		let mut capped: LetterMap<bool> = Default::default();
+		for i in 0..5usize {
+			match state[i] {
+				Correct => {
+					self.allowed[i] = LetterMap::filled(false);
+					self.allowed[i][word[i]] = true;
+					self.fixed[i] = Some(word[i]);
+					known_cnt[word[i]] += 1;
+				}
+				Occured => {
+					self.allowed[i][word[i]] = false;
+					known_cnt[word[i]] += 1;
+				}
+				Redundant => {
+					self.allowed[i][word[i]] = false;
+					capped[word[i]] = true;
+				}
+				Unknown => {}
+			}
+		}
+
+		for &c in word.iter() {
+			self.min_cnt[c] = self.min_cnt[c].max(known_cnt[c]);
+			if capped[c] {
+				self.max_cnt[c] = self.max_cnt[c].min(known_cnt[c]);
+			}
+		}
+	}
+
+	/// letter known Correct at `position`
+	pub fn fixed(&self, position: usize) -> Option<Letter> {
+		self.fixed[position]
+	}
+
+	/// whether `letter` may still appear at `position`
+	pub fn is_allowed(&self, position: usize, letter: Letter) -> bool {
+		if self.fixed[position].is_some() {
+			return self.fixed[position] == Some(letter);
+		}
+		// all occurrences may already be fixed elsewhere
+		let fixed_cnt = self.fixed.iter().filter(|&&c| c == Some(letter)).count() as u32;
+		return self.allowed[position][letter] && fixed_cnt < self.max_cnt[letter];
+	}
+
+	/// letters that may still appear at `position`
+	pub fn allowed(&self, position: usize) -> impl '_ + Iterator<Item = Letter> {
+		('A'..='Z').filter(move |&c| self.is_allowed(position, c))
+	}
+
+	/// minimum number of occurrences of `letter` in the answer
+	pub fn min_cnt(&self, letter: Letter) -> u32 {
+		self.min_cnt[letter]
+	}
+
+	/// maximum number of occurrences of `letter` in the answer, None if unknown
+	pub fn max_cnt(&self, letter: Letter) -> Option<u32> {
+		match self.max_cnt[letter] {
+			UNBOUNDED => None,
+			max => Some(max),
+		}
+	}
+
+	/// check `word` against the rules of `difficulty`
+	pub fn check(&self, word: &Word, difficulty: Difficulty) -> Result<(), GuessError> {
+		if difficulty == Difficulty::Normal {
+			return Ok(());
+		}
+
+		let mut word_cnt: LetterMap<u32> = Default::default();
+		for &c in word.iter() {
+			word_cnt[c] += 1;
+		}
+
+		// difficult: Correct letters stay, Occured letters are reused
+		for i in 0..5usize {
+			match self.fixed[i] {
+				Some(c) if c != word[i] => {
+					return Err(GuessError::MissingCorrect {
+						letter:   c,
+						position: i,
+					})
+				}
+				_ => {}
+			}
+		}
+		for c in 'A'..='Z' {
+			if word_cnt[c] < self.min_cnt[c] {
+				return Err(GuessError::MissingOccured {
+					letter: c
+				});
+			}
+		}
+		if difficulty == Difficulty::Hard {
+			return Ok(());
+		}
+
+		// strict: letter count bounds and positional exclusions
+		for &c in word.iter() {
+			if word_cnt[c] > self.max_cnt[c] {
+				return Err(match self.max_cnt[c] {
+					0 => GuessError::Absent {
+						letter: c
+					},
+					max => GuessError::TooMany {
+						letter: c,
+						max,
+					},
+				});
+			}
+		}
+		for i in 0..5usize {
+			if !self.allowed[i][word[i]] {
+				return Err(GuessError::Excluded {
+					letter:   word[i],
+					position: i,
+				});
+			}
+		}
+
+		return Ok(());
+	}
+
+	/// whether `word` can still be the answer
+	pub fn matches(&self, word: &Word) -> bool {
+		self.check(word, Difficulty::Strict).is_ok()
+	}
+}
@@ -0,0 +1,121 @@
+use {
+	crate::{plate::*, statistic::Statistic},
+	anyhow::Result,
+	serde::{Deserialize, Serialize},
+	std::path::Path,
+};
+
+/// Totals of a single user
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+	pub user:             String,
+	pub games:            u64,
+	pub wins:             u64,
+	/// 0 ~ 1
+	pub win_rate:         f64,
+	pub average_attempts: f64,
+	pub current_streak:   u64,
+	pub max_streak:       u64,
+}
+
+/// First solve of the daily answer by a user
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct DailySolve {
+	pub user:    String,
+	pub guesses: usize,
+}
+
+/// Ranking of users sharing a state directory
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Leaderboard {
+	/// ordered by win rate, then average attempts
+	pub entries: Vec<LeaderboardEntry>,
+	pub daily:   Option<String>,
+	/// ordered by guesses
+	pub solves:  Vec<DailySolve>,
+}
+
+impl LeaderboardEntry {
+	pub fn new(user: &str, s: &Statistic) -> Self {
+		let games = s.success_cnt() + s.fail_cnt();
+		Self {
+			user: user.to_string(),
+			games,
+			wins: s.success_cnt(),
+			win_rate: match games {
+				0 => 0.0,
+				_ => s.success_cnt() as f64 / games as f64,
+			},
+			average_attempts: s.success_attempt_average(),
+			current_streak: s.current_streak(),
+			max_streak: s.max_streak(),
+		}
+	}
+}
+
+impl Leaderboard {
+	/// rank `users`, with solves of the `daily` answer if given
+	pub fn new(users: &[(String, Statistic)], daily: Option<&Word>) -> Self {
+		let mut entries: Vec<LeaderboardEntry> = users
+			.iter()
+			.map(|(user, s)| LeaderboardEntry::new(user, s))
+			.collect();
+		entries.sort_by(|a, b| {
+			b.win_rate
+				.total_cmp(&a.win_rate)
+				.then(a.average_attempts.total_cmp(&b.average_attempts))
+				.then(b.wins.cmp(&a.wins))
+				.then(a.user.cmp(&b.user))
+		});
+
+		let daily = daily.map(word_to_str);
+		let mut solves: Vec<DailySolve> = match &daily {
+			None => vec![],
+			Some(answer) => users
+				.iter()
+				.filter_map(|(user, s)| {
+					let game = s.games().iter().find(|g| &g.answer == answer)?;
+					game.is_win().then(|| DailySolve {
+						user:    user.clone(),
+						guesses: game.guesses.len(),
+					})
+				})
+				.collect(),
+		};
+		solves.sort_by(|a, b| a.guesses.cmp(&b.guesses).then(a.user.cmp(&b.user)));
+
+		return Self {
+			entries,
+			daily,
+			solves,
+		};
+	}
+
+	pub fn load_from_json(json_str: &str) -> Result<Self> {
+		Ok(serde_json::from_str(json_str)?)
+	}
+
+	pub fn store_to_json(&self) -> String {
+		serde_json::to_string_pretty(self).unwrap()
+	}
+}
+
+/// statistic of every `{user}.json` state file in `dir`, ordered by user
+pub fn load_dir(dir: &Path) -> Result<Vec<(String, Statistic)>> {
+	let mut users = vec![];
+	for entry in std::fs::read_dir(dir)? {
+		let path = entry?.path();
+		if path.extension().and_then(|e| e.to_str()) != Some("json") {
+			continue;
+		}
+		let user = match path.file_stem().and_then(|s| s.to_str()) {
+			Some(user) => user.to_string(),
+			None => continue,
+		};
+		let statistic = Statistic::load_from_json(&std::fs::read_to_string(&path)?)
+			.map_err(|e| e.context(format!("invalid state file {}", path.display())))?;
+		users.push((user, statistic));
+	}
+	users.sort_by(|a, b| a.0.cmp(&b.0));
+	return Ok(users);
+}
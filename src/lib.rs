@@ -1,7 +1,18 @@
+pub mod achievement;
+pub mod analysis;
+pub mod archive;
+pub mod assist;
 pub mod builtin_words;
+pub mod challenge;
+pub mod completion;
 pub mod config;
 pub mod interactor;
+pub mod knowledge;
+pub mod leaderboard;
+pub mod pattern;
 pub mod plate;
+pub mod race;
+pub mod server;
 pub mod statistic;
 pub mod util;
 pub mod word_gen;
@@ -1,55 +1,482 @@
 use {
-	anyhow::{Error, Result},
+	anyhow::{anyhow, Error, Result},
+	std::time::{Duration, Instant},
 	wordle::{
-		config::{self, WordSrc},
+		analysis::{analyze, solve},
+		archive::archive,
+		assist::{AssistInput, Assistant, SUGGESTIONS},
+		challenge::Challenge,
+		completion::write_completions,
+		config::{self, ChallengeCommand, Command, CompleteKind, Config, ConfigCommand, WordSrc},
 		interactor::*,
+		leaderboard::{self, Leaderboard},
+		pattern::matrix::{default_cache_path, PatternMatrix},
 		plate::*,
-		statistic::Statistic,
+		race::{net::RaceClient, ServerMsg},
+		statistic::{PlayerStatistic, Statistic},
 		util::loop_on_err_with,
 		word_gen::*,
 	},
 };
 
-fn main() -> Result<()> {
-	let is_tty = atty::is(atty::Stream::Stdout);
-	let config = config::config()?;
-	let inter: &dyn Interactor = if is_tty { &Tty::new() } else { &Cmd::new() };
+fn interactor(is_tty: bool) -> Box<dyn Interactor + Send> {
+	match is_tty {
+		true => Box::new(Tty::new()),
+		false => Box::new(Cmd::new()),
+	}
+}
 
-	let word_generator: &mut dyn Iterator<Item = Word> = match config.word_src {
-		WordSrc::Select(word) => &mut std::iter::repeat(word).take(1),
-		WordSrc::Ask => &mut RepeatReader::new(reader_from_set(&config.set_final, inter)),
-		WordSrc::Random(seed, date) => {
-			&mut RepeatReader::new(rand_words(&config.list_final, seed, date))
-		}
-	};
-	let mut statistic = match &config.state_src {
-		None => Statistic::new(),
-		Some(src) => Statistic::load_from_file(&std::path::Path::new(src))?,
-	};
-	let mut read_acceptable = reader_from_set(&config.set_acceptable, inter);
+/// longest wait for opponents to finish after the player's game of a race
+const RACE_WAIT: Duration = Duration::from_secs(120);
 
-	while let Some(word) = word_generator.next() {
-		let mut plate = Plate::new(&word, config.difficult);
-		inter.new_round();
-		while !plate.is_win() && plate.count() < 6 {
+/// Settings shared by every game of a session
+struct Session<'a> {
+	config:   &'a Config,
+	inter:    &'a dyn Interactor,
+	race:     Option<&'a RaceClient>,
+	/// patterns for the analysis of each game
+	matrix:   Option<&'a PatternMatrix>,
+	/// end of a time attack
+	deadline: Option<Instant>,
+	/// day of the archive puzzle being replayed
+	archive:  Option<u32>,
+}
+
+impl Session<'_> {
+	/// play a whole game of `word` and record it in `statistic`
+	fn play(
+		&self,
+		word: &Word,
+		player: Option<&str>,
+		read_acceptable: &mut dyn FnMut() -> Result<Word>,
+		statistic: &mut Statistic,
+	) -> Result<Plate> {
+		let (config, inter) = (self.config, self.inter);
+		let mut plate = Plate::with_max_guesses(word, config.difficulty, config.max_guesses);
+		match player {
+			None => inter.new_round(),
+			Some(player) => inter.print_turn(player),
+		}
+		while !plate.is_over() {
+			let start = Instant::now();
+			// the earlier one of countdown and time attack
+			let deadline = [config.countdown.map(|c| start + c), self.deadline]
+				.into_iter()
+				.flatten()
+				.min();
 			loop_on_err_with(
 				|| {
-					plate.guess(&read_acceptable()?)?;
+					let word = read_acceptable()?;
+					match deadline {
+						Some(deadline) if Instant::now() > deadline => plate.time_up(),
+						_ if config.timed => plate.guess_timed(&word, start.elapsed())?,
+						_ => plate.guess(&word)?,
+					}
 					return Ok(());
 				},
 				|e: Error| {
 					inter.print_err(e);
 				},
 			);
+			if plate.is_time_up() {
+				break;
+			}
 			inter.print_guess(&plate);
+			if let Some(race) = self.race {
+				race.send_guess(&plate.history().last().unwrap().1)?;
+			}
+		}
+		let analysis = config.analyze.then(|| {
+			analyze(
+				&plate,
+				&config.list_final,
+				&config.set_acceptable,
+				self.matrix,
+			)
+		});
+		match &analysis {
+			Some(analysis) => statistic.add_analyzed_plate(&plate, analysis.clone()),
+			None => statistic.add_plate(&plate),
+		}
+		if let Some(day) = self.archive {
+			statistic.mark_archived(day);
+		}
+		let achievements = match config.achievements {
+			true => statistic.unlock_achievements(&plate),
+			false => vec![],
+		};
+
+		inter.print_result(&plate, &achievements);
+		if let Some(race) = self.race {
+			// opponents may have left their game open
+			let deadline = Instant::now() + RACE_WAIT;
+			while !race.is_finished() && Instant::now() < deadline {
+				std::thread::sleep(Duration::from_millis(100));
+			}
+			inter.print_race(&race.board());
+		}
+		if let Some(analysis) = &analysis {
+			inter.print_analysis(analysis);
+		}
+		return Ok(plate);
+	}
+}
+
+/// pick a past daily puzzle, returns (day, answer)
+fn pick_archive(config: &Config, inter: &dyn Interactor) -> Result<(u32, Word)> {
+	let (seed, last) = match config.word_src {
+		WordSrc::Random(seed, day) => (seed, day),
+		_ => unreachable!(),
+	};
+	let statistic = match &config.state_src {
+		None => Statistic::new(),
+		Some(path) => Statistic::load_from_file(std::path::Path::new(path))?,
+	};
+	let days = archive(&config.list_final, seed, last, &statistic);
+	inter.print_archive(&days);
+	let day = loop_on_err_with(
+		|| inter.read_day(days.len() as u32),
+		|e: Error| {
+			inter.print_err(e);
+		},
+	);
+	return Ok((day, days[day as usize - 1].answer));
+}
+
+/// print the leaderboard of a state directory
+fn print_leaderboard(
+	dir: &str,
+	daily: Option<(u64, u32)>,
+	json: bool,
+	config: &Config,
+	inter: &dyn Interactor,
+) -> Result<()> {
+	let users = leaderboard::load_dir(std::path::Path::new(dir))?;
+	let daily = match daily {
+		None => None,
+		Some((seed, day)) => Some(rand_words(&config.list_final, seed, day)()?),
+	};
+	let leaderboard = Leaderboard::new(&users, daily.as_ref());
+	match json {
+		true => println!("{}", leaderboard.store_to_json()),
+		false => inter.print_leaderboard(&leaderboard),
+	}
+	return Ok(());
+}
+
+/// the cache built by `wordle matrix` if it matches the word lists
+/// a missing or unreadable cache only means patterns are computed
+fn cached_matrix(config: &Config) -> Option<PatternMatrix> {
+	let path = default_cache_path()?;
+	return PatternMatrix::open(&path, &config.set_acceptable, &config.list_final)
+		.ok()
+		.flatten();
+}
+
+/// narrow down the answer of a puzzle played elsewhere by the feedback read from stdin
+fn assist(config: &Config, inter: &dyn Interactor) -> Result<()> {
+	let matrix = cached_matrix(config);
+	let mut assistant = Assistant::new(&config.list_final, &config.set_acceptable, matrix.as_ref());
+	let mut buf = String::new();
+	while !assistant.is_solved() {
+		buf.clear();
+		if std::io::stdin().read_line(&mut buf)? == 0 {
+			break;
+		}
+		if buf.trim().is_empty() {
+			continue;
+		}
+		match buf
+			.parse::<AssistInput>()
+			.and_then(|input| assistant.apply(input))
+		{
+			Ok(()) => inter.print_assist(&assistant, &assistant.suggestions(SUGGESTIONS)),
+			Err(e) => inter.print_err(e.into()),
+		}
+	}
+	return Ok(());
+}
+
+/// print the statistic of the state file, or of a player in it
+fn print_stats(config: &Config, player: Option<&str>, inter: &dyn Interactor) -> Result<()> {
+	let path = match &config.state_src {
+		Some(path) => std::path::Path::new(path),
+		None => return Err(anyhow!("no state file, use --state")),
+	};
+	let statistic = match player {
+		None => Statistic::load_from_file(path)?,
+		Some(name) => {
+			let mut statistics = PlayerStatistic::load_from_file(path)?;
+			if statistics.get(name).is_none() {
+				return Err(anyhow!("player {} not found", name));
+			}
+			std::mem::take(statistics.player_mut(name))
+		}
+	};
+	inter.print_statistic(&statistic);
+	return Ok(());
+}
+
+fn main() -> Result<()> {
+	let is_tty = atty::is(atty::Stream::Stdout);
+	let config = config::config()?;
+	let inter_box = interactor(is_tty);
+	let inter: &dyn Interactor = inter_box.as_ref();
+
+	match &config.command {
+		Some(Command::Leaderboard {
+			dir,
+			seed,
+			day,
+			json,
+		}) => {
+			let daily = day.map(|day| (seed.unwrap_or(0), day));
+			return print_leaderboard(dir, daily, *json, &config, inter);
+		}
+		Some(Command::Challenge {
+			action:
+				ChallengeCommand::Create {
+					word,
+					difficult,
+					strict,
+					max_guesses,
+				},
+		}) => {
+			let difficulty = Difficulty::from_flags(*difficult, *strict);
+			let challenge = Challenge::new(word_from_str(word)?, difficulty, *max_guesses)?;
+			challenge.check(&config.set_acceptable)?;
+			println!("{}", challenge.encode());
+			return Ok(());
+		}
+		Some(Command::Config {
+			action: ConfigCommand::Show,
+		}) => {
+			for setting in config::settings()? {
+				match setting.value {
+					serde_json::Value::Null => println!("# {} is not set", setting.key),
+					value => println!("{} = {}  # {}", setting.key, value, setting.source),
+				}
+			}
+			return Ok(());
+		}
+		Some(Command::Stats {
+			player,
+		}) => {
+			return print_stats(&config, player.as_deref(), inter);
+		}
+		Some(Command::Solve {
+			word,
+			difficult,
+			strict,
+		}) => {
+			let difficulty = Difficulty::from_flags(*difficult, *strict);
+			let matrix = cached_matrix(&config);
+			let plate = solve(
+				&word_from_str(word)?,
+				&config.list_final,
+				&config.set_acceptable,
+				matrix.as_ref(),
+				difficulty,
+			)?;
+			for (word, state) in plate.history() {
+				let state: String = state.iter().map(LetterState::to_char).collect();
+				println!("{} {}", word, state);
+			}
+			inter.print_result(&plate, &[]);
+			return Ok(());
+		}
+		Some(Command::Matrix {
+			cache,
+		}) => {
+			let path = match cache {
+				Some(path) => std::path::PathBuf::from(path),
+				None => default_cache_path().ok_or(anyhow!("no cache directory, use --cache"))?,
+			};
+			let matrix =
+				PatternMatrix::load_or_build(&path, &config.set_acceptable, &config.list_final)?;
+			println!(
+				"{} {} {} {}",
+				path.display(),
+				matrix.guesses().len(),
+				matrix.answers().len(),
+				match matrix.is_mapped() {
+					true => "CACHED",
+					false => "BUILT",
+				}
+			);
+			return Ok(());
+		}
+		Some(Command::Assist) => {
+			return assist(&config, inter);
+		}
+		Some(Command::Lists {
+			print_final,
+			acceptable,
+		}) => {
+			match (print_final, acceptable) {
+				(true, _) => config.list_final.iter().for_each(|w| println!("{}", w)),
+				(false, true) => {
+					let mut list: Vec<&Word> = config.set_acceptable.iter().collect();
+					list.sort();
+					list.iter().for_each(|w| println!("{}", w));
+				}
+				(false, false) => {
+					println!("acceptable {}", config.set_acceptable.len());
+					println!("final {}", config.list_final.len());
+				}
+			}
+			return Ok(());
+		}
+		Some(Command::Completions {
+			shell,
+		}) => {
+			write_completions(*shell, &mut config::cli(), &mut std::io::stdout())?;
+			return Ok(());
+		}
+		Some(Command::Manpage) => {
+			clap_mangen::Man::new(config::cli()).render(&mut std::io::stdout())?;
+			return Ok(());
+		}
+		Some(Command::Complete {
+			kind,
+			prefix,
+		}) => {
+			let candidates = match kind {
+				CompleteKind::Word => {
+					let prefix = prefix.to_uppercase();
+					let mut words: Vec<String> = config
+						.set_acceptable
+						.iter()
+						.map(word_to_str)
+						.filter(|w| w.starts_with(&prefix))
+						.map(|w| w.to_lowercase())
+						.collect();
+					words.sort();
+					words
+				}
+				CompleteKind::Profile => config::profiles()?,
+			};
+			candidates.iter().for_each(|c| println!("{}", c));
+			return Ok(());
+		}
+		Some(Command::Play(_) | Command::Daily(_)) | None => {}
+	}
+
+	let race = match &config.word_src {
+		WordSrc::Race(url) => {
+			let race_inter = interactor(is_tty);
+			Some(RaceClient::join(url, &config.name, move |msg| match msg {
+				ServerMsg::Waiting {
+					players,
+					needed,
+				} => race_inter.print_waiting(&players, needed),
+				// opponents' progress in real time, non-tty output only has the standings
+				ServerMsg::Board {
+					players,
+				} if is_tty => race_inter.print_race(&players),
+				ServerMsg::Error {
+					message,
+				} => race_inter.print_err(anyhow!(message)),
+				_ => {}
+			})?)
+		}
+		_ => None,
+	};
+
+	let archive = match config.archive {
+		true => Some(pick_archive(&config, inter)?),
+		false => None,
+	};
+	let deadline = config.time_attack.map(|limit| Instant::now() + limit);
+	let matrix = config.analyze.then(|| cached_matrix(&config)).flatten();
+	let session = Session {
+		config: &config,
+		inter,
+		race: race.as_ref(),
+		matrix: matrix.as_ref(),
+		deadline,
+		archive: archive.map(|(day, _)| day),
+	};
+	let answers_per_round = match config.alternate {
+		true => config.players.len(),
+		false => 1,
+	};
+	let word_src = match archive {
+		Some((_, word)) => WordSrc::Select(word),
+		None => config.word_src.clone(),
+	};
+	let round_generator: &mut dyn Iterator<Item = Vec<Word>> = match word_src {
+		WordSrc::Select(word) => &mut std::iter::once(vec![word]),
+		WordSrc::Ask => &mut RepeatReader::new(round_words(
+			reader_from_set(&config.set_final, inter),
+			answers_per_round,
+		)),
+		WordSrc::Random(seed, date) => match deadline {
+			None => &mut RepeatReader::new(round_words(
+				rand_words(&config.list_final, seed, date),
+				answers_per_round,
+			)),
+			// no prompts between answers of a time attack
+			Some(deadline) => {
+				let mut next_word = rand_words(&config.list_final, seed, date);
+				&mut std::iter::from_fn(move || Some(vec![next_word().ok()?]))
+					.take_while(move |_| Instant::now() < deadline)
+			}
+		},
+		WordSrc::Race(_) => {
+			let race = race.as_ref().unwrap();
+			&mut std::iter::once(vec![rand_words(
+				&config.list_final,
+				race.seed(),
+				race.day(),
+			)()?])
+		}
+	};
+	let state_path = config.state_src.as_ref().map(std::path::Path::new);
+	let mut read_acceptable = reader_from_set(&config.set_acceptable, inter);
+
+	if config.players.is_empty() {
+		let mut statistic = match state_path {
+			None => Statistic::new(),
+			Some(path) => Statistic::load_from_file(path)?,
+		};
+		let (mut solved, mut played) = (0u64, 0u64);
+		for words in round_generator {
+			let plate = session.play(&words[0], None, &mut read_acceptable, &mut statistic)?;
+			played += 1;
+			if plate.is_win() {
+				solved += 1;
+			}
+			if config.stats {
+				inter.print_statistic(&statistic);
+			}
+			if let Some(path) = state_path {
+				statistic.store_to_file(path)?;
+			}
 		}
-		statistic.add_plate(&plate);
-		inter.print_result(&plate);
-		if config.stats {
-			inter.print_statistic(&statistic);
+		if config.time_attack.is_some() {
+			inter.print_time_attack(solved, played);
 		}
-		if let Some(path) = &config.state_src {
-			statistic.store_to_file(&std::path::Path::new(path))?;
+	} else {
+		let mut statistics = match state_path {
+			None => PlayerStatistic::new(),
+			Some(path) => PlayerStatistic::load_from_file(path)?,
+		};
+		for words in round_generator {
+			let mut round = vec![];
+			for (i, player) in config.players.iter().enumerate() {
+				let plate = session.play(
+					&words[i % words.len()],
+					Some(player),
+					&mut read_acceptable,
+					statistics.player_mut(player),
+				)?;
+				round.push((player.clone(), plate));
+			}
+			inter.print_scoreboard(&round, &statistics);
+			if let Some(path) = state_path {
+				statistics.store_to_file(path)?;
+			}
 		}
 	}
 
@@ -0,0 +1,99 @@
+use {crate::plate::*, std::fmt, LetterState::*};
+
+pub mod matrix;
+
+/// number of possible feedbacks of a guess
+pub const PATTERNS: usize = 243;
+
+/// Feedback of a guess packed in one byte, for solvers scoring many pairs
+/// digits in base 3 with the first letter most significant: 2 Correct, 1 Occured, 0 Redundant
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Debug, Default)]
+pub struct Pattern(u8);
+
+impl Pattern {
+	/// feedback of a winning guess
+	pub const WIN: Pattern = Pattern(PATTERNS as u8 - 1);
+
+	/// Unknown is packed as Redundant, it never appears in the result of `evaluate`
+	pub fn new(state: &WordState) -> Self {
+		let value = state.iter().fold(0u8, |acc, s| {
+			acc * 3
+				+ match s {
+					Correct => 2,
+					Occured => 1,
+					_ => 0,
+				}
+		});
+		return Pattern(value);
+	}
+
+	/// None if `value` is not below PATTERNS
+	pub fn from_u8(value: u8) -> Option<Self> {
+		((value as usize) < PATTERNS).then_some(Pattern(value))
+	}
+
+	pub fn as_u8(&self) -> u8 {
+		self.0
+	}
+	/// 0 ~ 242, e.g. to index counters of each feedback
+	pub fn index(&self) -> usize {
+		self.0 as usize
+	}
+
+	pub fn state(&self) -> WordState {
+		let mut state = [Redundant; 5];
+		let mut value = self.0;
+		for s in state.iter_mut().rev() {
+			*s = match value % 3 {
+				2 => Correct,
+				1 => Occured,
+				_ => Redundant,
+			};
+			value /= 3;
+		}
+		return state;
+	}
+
+	pub fn is_win(&self) -> bool {
+		*self == Self::WIN
+	}
+}
+
+impl fmt::Display for Pattern {
+	/// letter codes of `LetterState::to_char`, e.g. GYRRG
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for s in self.state() {
+			write!(f, "{}", s.to_char())?;
+		}
+		return Ok(());
+	}
+}
+
+/// packed feedback of `guess` against `answer`, same as `evaluate`
+pub fn evaluate_pattern(guess: &Word, answer: &Word) -> Pattern {
+	Pattern::new(&evaluate(guess, answer))
+}
+
+/// feedback of `guess` against each of `answers` into `out`, which must be as long
+pub fn evaluate_into(guess: &Word, answers: &[Word], out: &mut [Pattern]) {
+	assert_eq!(answers.len(), out.len(), "one pattern per answer");
+	for (answer, pattern) in answers.iter().zip(out.iter_mut()) {
+		*pattern = evaluate_pattern(guess, answer);
+	}
+}
+
+/// number of `answers` giving each feedback to `guess`, indexed by `Pattern::index`
+pub fn pattern_counts(guess: &Word, answers: &[Word]) -> [u32; PATTERNS] {
+	let mut counts = [0u32; PATTERNS];
+	for answer in answers {
+		counts[evaluate_pattern(guess, answer).index()] += 1;
+	}
+	return counts;
+}
+
+/// expected number of answers left by a guess with these `counts` of each feedback
+pub fn expected_size(counts: &[u32; PATTERNS]) -> f64 {
+	let total: u64 = counts.iter().map(|&n| n as u64).sum();
+	let square_sum: u64 = counts.iter().map(|&n| (n as u64) * (n as u64)).sum();
+	return square_sum as f64 / total.max(1) as f64;
+}
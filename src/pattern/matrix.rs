@@ -0,0 +1,224 @@
+use {
+	super::*,
+	std::collections::{HashMap, HashSet},
+};
+#[cfg(not(target_arch = "wasm32"))]
+use {
+	memmap2::Mmap,
+	rayon::prelude::*,
+	std::{
+		fs::File,
+		io::{self, Write},
+		path::{Path, PathBuf},
+	},
+};
+
+/// start of a cache file, changed along with the layout
+#[cfg(not(target_arch = "wasm32"))]
+const MAGIC: &[u8; 8] = b"WDLPAT01";
+/// magic, key (8 bytes), guesses and answers (4 bytes each), all little endian
+#[cfg(not(target_arch = "wasm32"))]
+const HEADER: usize = 24;
+
+/// Patterns of every acceptable guess against every final answer, one byte each
+/// row `i` holds guess `i` against every answer
+pub struct PatternMatrix {
+	guesses:      Vec<Word>,
+	answers:      Vec<Word>,
+	/// index of each answer in `answers`
+	answer_index: HashMap<Word, usize>,
+	data:         Data,
+}
+
+enum Data {
+	Built(Vec<u8>),
+	/// the whole cache file, patterns start after HEADER
+	#[cfg(not(target_arch = "wasm32"))]
+	Mapped(Mmap),
+}
+
+/// FNV-1a of both lists, so a cache of other lists is never used
+pub fn lists_key(guesses: &[Word], answers: &[Word]) -> u64 {
+	let mut hash = 0xcbf2_9ce4_8422_2325u64;
+	let letters = guesses
+		.iter()
+		.flat_map(|w| w.iter())
+		.chain(std::iter::once(&'|'))
+		.chain(answers.iter().flat_map(|w| w.iter()));
+	for &c in letters {
+		hash = (hash ^ c as u64).wrapping_mul(0x0100_0000_01b3);
+	}
+	return hash;
+}
+
+/// $XDG_CACHE_HOME/wordle/patterns.bin, or under ~/.cache
+#[cfg(not(target_arch = "wasm32"))]
+pub fn default_cache_path() -> Option<PathBuf> {
+	let dir = match std::env::var_os("XDG_CACHE_HOME") {
+		Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+		_ => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+	};
+	return Some(dir.join("wordle").join("patterns.bin"));
+}
+
+/// rows are in alphabetical order, so the cache does not depend on the order of a set
+fn sorted(set_acceptable: &HashSet<Word>) -> Vec<Word> {
+	let mut guesses: Vec<Word> = set_acceptable.iter().copied().collect();
+	guesses.sort();
+	return guesses;
+}
+
+impl PatternMatrix {
+	/// compute every row, in parallel where threads are available
+	pub fn build(set_acceptable: &HashSet<Word>, answers: &[Word]) -> Self {
+		let guesses = sorted(set_acceptable);
+		let mut data = vec![0u8; guesses.len() * answers.len()];
+		if !answers.is_empty() {
+			#[cfg(not(target_arch = "wasm32"))]
+			let rows = data.par_chunks_mut(answers.len()).zip(guesses.par_iter());
+			#[cfg(target_arch = "wasm32")]
+			let rows = data.chunks_mut(answers.len()).zip(guesses.iter());
+			rows.for_each(|(row, guess)| {
+				for (cell, answer) in row.iter_mut().zip(answers) {
+					*cell = evaluate_pattern(guess, answer).as_u8();
+				}
+			});
+		}
+		return Self::with_data(guesses, answers, Data::Built(data));
+	}
+
+	fn with_data(guesses: Vec<Word>, answers: &[Word], data: Data) -> Self {
+		Self {
+			guesses,
+			answers: answers.to_vec(),
+			answer_index: answers.iter().enumerate().map(|(i, w)| (*w, i)).collect(),
+			data,
+		}
+	}
+
+	/// map the cache file at `path` if it was built from the same lists
+	/// None if it is missing, stale or truncated, the patterns are not read here
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn open(
+		path: &Path,
+		set_acceptable: &HashSet<Word>,
+		answers: &[Word],
+	) -> io::Result<Option<Self>> {
+		let guesses = sorted(set_acceptable);
+		let file = match File::open(path) {
+			Ok(file) => file,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(e),
+		};
+		// SAFETY: the cache file is only replaced by renaming, never written in place
+		let map = unsafe { Mmap::map(&file)? };
+		let mut header = [0u8; HEADER];
+		header[..8].copy_from_slice(MAGIC);
+		header[8..16].copy_from_slice(&lists_key(&guesses, answers).to_le_bytes());
+		header[16..20].copy_from_slice(&(guesses.len() as u32).to_le_bytes());
+		header[20..24].copy_from_slice(&(answers.len() as u32).to_le_bytes());
+		if map.len() != HEADER + guesses.len() * answers.len() || map[..HEADER] != header {
+			return Ok(None);
+		}
+		return Ok(Some(Self::with_data(guesses, answers, Data::Mapped(map))));
+	}
+
+	/// write to `path` through a temporary file, so readers never see a partial one
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn store(&self, path: &Path) -> io::Result<()> {
+		if let Some(dir) = path.parent() {
+			std::fs::create_dir_all(dir)?;
+		}
+		let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+		let mut file = io::BufWriter::new(File::create(&tmp)?);
+		file.write_all(MAGIC)?;
+		file.write_all(&lists_key(&self.guesses, &self.answers).to_le_bytes())?;
+		file.write_all(&(self.guesses.len() as u32).to_le_bytes())?;
+		file.write_all(&(self.answers.len() as u32).to_le_bytes())?;
+		file.write_all(self.bytes())?;
+		file.into_inner()?.sync_all()?;
+		return std::fs::rename(&tmp, path);
+	}
+
+	/// map the cache at `path`, or build and store it when missing or stale
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn load_or_build(
+		path: &Path,
+		set_acceptable: &HashSet<Word>,
+		answers: &[Word],
+	) -> io::Result<Self> {
+		if let Some(matrix) = Self::open(path, set_acceptable, answers)? {
+			return Ok(matrix);
+		}
+		let matrix = Self::build(set_acceptable, answers);
+		matrix.store(path)?;
+		return Ok(matrix);
+	}
+
+	fn bytes(&self) -> &[u8] {
+		match &self.data {
+			Data::Built(data) => data,
+			#[cfg(not(target_arch = "wasm32"))]
+			Data::Mapped(map) => &map[HEADER..],
+		}
+	}
+	pub fn is_mapped(&self) -> bool {
+		!matches!(self.data, Data::Built(_))
+	}
+
+	pub fn guesses(&self) -> &[Word] {
+		&self.guesses
+	}
+	pub fn answers(&self) -> &[Word] {
+		&self.answers
+	}
+	pub fn guess_index(&self, guess: &Word) -> Option<usize> {
+		self.guesses.binary_search(guess).ok()
+	}
+	pub fn answer_index(&self, answer: &Word) -> Option<usize> {
+		self.answer_index.get(answer).copied()
+	}
+
+	/// packed patterns of guess `i` against every answer, see `Pattern::from_u8`
+	pub fn row(&self, i: usize) -> &[u8] {
+		let len = self.answers.len();
+		return &self.bytes()[i * len..(i + 1) * len];
+	}
+	/// None if the cache file is damaged there
+	pub fn get(&self, guess: usize, answer: usize) -> Option<Pattern> {
+		Pattern::from_u8(self.row(guess)[answer])
+	}
+
+	/// number of `answers`, given as indices, giving each feedback to guess `i`
+	/// a damaged byte of the cache file is not counted
+	pub fn counts(&self, i: usize, answers: &[usize]) -> [u32; PATTERNS] {
+		let row = self.row(i);
+		let mut counts = [0u32; PATTERNS];
+		for &answer in answers {
+			if let Some(count) = counts.get_mut(row[answer] as usize) {
+				*count += 1;
+			}
+		}
+		return counts;
+	}
+
+	/// expected remaining `candidates` after each of `guesses`, as `expected_size`
+	/// None unless every word is in the matrix
+	pub fn expected_remaining(
+		&self,
+		guesses: &HashSet<Word>,
+		candidates: &[Word],
+	) -> Option<Vec<(Word, f64)>> {
+		let answers: Vec<usize> = candidates
+			.iter()
+			.map(|w| self.answer_index(w))
+			.collect::<Option<_>>()?;
+		return guesses
+			.iter()
+			.map(|guess| {
+				let i = self.guess_index(guess)?;
+				Some((*guess, expected_size(&self.counts(i, &answers))))
+			})
+			.collect();
+	}
+}
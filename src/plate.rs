@@ -1,6 +1,6 @@
 use {
-	crate::util::LetterMap,
-	anyhow::{anyhow, Result},
+	crate::{knowledge::Knowledge, util::LetterMap},
+	std::{fmt, ops::Deref, str::FromStr, time::Duration},
 	LetterState::*,
 };
 
@@ -13,32 +13,212 @@ pub enum LetterState {
 	Unknown,
 }
 
-pub type Word = [Letter; 5];
+/// A five-letter word, guaranteed to consist of uppercase ASCII letters only
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct Word([Letter; 5]);
 pub type WordState = [LetterState; 5];
 
-pub fn word_from_str(s: &str) -> Result<Word> {
-	s.to_ascii_uppercase()
-		.chars()
-		.collect::<Vec<char>>()
-		.try_into()
-		.map_err(|_| anyhow!("invalid word: {:?}", s))
+/// Reasons for rejecting a string as a `Word`
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum WordError {
+	/// number of letters is not 5
+	Length(usize),
+	/// (letter, position): a non-alphabetic letter
+	Letter(char, usize),
+}
+
+impl fmt::Display for WordError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			WordError::Length(len) => write!(f, "expected 5 letters, found {}", len),
+			WordError::Letter(c, pos) => {
+				write!(f, "invalid letter {:?} at position {}", c, pos + 1)
+			}
+		}
+	}
+}
+
+impl std::error::Error for WordError {}
+
+impl Word {
+	/// validate and uppercase `letters`
+	pub fn new(letters: [Letter; 5]) -> Result<Word, WordError> {
+		let mut word = letters;
+		for (i, c) in word.iter_mut().enumerate() {
+			if !c.is_ascii_alphabetic() {
+				return Err(WordError::Letter(*c, i));
+			}
+			*c = c.to_ascii_uppercase();
+		}
+		return Ok(Word(word));
+	}
+
+	pub fn letters(&self) -> &[Letter; 5] {
+		&self.0
+	}
+}
+
+impl FromStr for Word {
+	type Err = WordError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let letters: Vec<char> = s.chars().collect();
+		let len = letters.len();
+		Word::new(letters.try_into().map_err(|_| WordError::Length(len))?)
+	}
+}
+
+impl Deref for Word {
+	type Target = [Letter; 5];
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl fmt::Display for Word {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.iter().try_for_each(|c| write!(f, "{}", c))
+	}
+}
+
+pub fn word_from_str(s: &str) -> Result<Word, WordError> {
+	s.parse()
 }
 
 pub fn word_to_str(s: &Word) -> String {
-	s.iter().collect()
+	s.to_string()
+}
+
+/// Reasons for rejecting a guess
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum GuessError {
+	/// (input, error): input is not a word
+	Invalid(String, WordError),
+	/// word is not in the word list
+	NotAcceptable(Word),
+	/// difficult mode: a Correct letter is not kept at its position
+	MissingCorrect { letter: Letter, position: usize },
+	/// difficult mode: an Occured letter is not reused
+	MissingOccured { letter: Letter },
+	/// strict mode: letter is known not to be at position
+	Excluded { letter: Letter, position: usize },
+	/// strict mode: letter is known not to be in the answer
+	Absent { letter: Letter },
+	/// strict mode: letter is used more than `max` times
+	TooMany { letter: Letter, max: u32 },
+	/// no more guesses are allowed
+	GameOver,
+}
+
+impl fmt::Display for GuessError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			GuessError::Invalid(input, e) => write!(f, "{:?} is not a word: {}", input, e),
+			GuessError::NotAcceptable(word) => write!(f, "{} is not in the word list", word),
+			GuessError::MissingCorrect {
+				letter,
+				position,
+			} => {
+				write!(f, "letter {} must be at position {}", letter, position + 1)
+			}
+			GuessError::MissingOccured {
+				letter,
+			} => write!(f, "letter {} must be used", letter),
+			GuessError::Excluded {
+				letter,
+				position,
+			} => {
+				write!(
+					f,
+					"letter {} cannot be at position {}",
+					letter,
+					position + 1
+				)
+			}
+			GuessError::Absent {
+				letter,
+			} => write!(f, "letter {} is not in the answer", letter),
+			GuessError::TooMany {
+				letter,
+				max,
+			} => {
+				write!(f, "letter {} occurs at most {} time(s)", letter, max)
+			}
+			GuessError::GameOver => write!(f, "the game is already over"),
+		}
+	}
+}
+
+impl std::error::Error for GuessError {}
+
+/// Rule level of difficult mode
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Difficulty {
+	/// any acceptable word
+	#[default]
+	Normal,
+	/// Correct letters stay, Occured letters are reused
+	Hard,
+	/// every known constraint must hold (a.k.a. ultra-hard)
+	Strict,
 }
 
-pub fn word_eq(lhs: &Word, rhs: &Word) -> bool {
-	lhs.iter().zip(rhs.iter()).all(|(a, b)| a == b)
+impl Difficulty {
+	/// level from command line flags, `strict` implies `difficult`
+	pub fn from_flags(difficult: bool, strict: bool) -> Self {
+		match (strict, difficult) {
+			(true, _) => Difficulty::Strict,
+			(false, true) => Difficulty::Hard,
+			(false, false) => Difficulty::Normal,
+		}
+	}
 }
 
+/// feedback of `guess` against `answer`, pure and allocation free
+/// see `pattern` to pack it into a byte
+pub fn evaluate(guess: &Word, answer: &Word) -> WordState {
+	let mut word_state: WordState = Default::default();
+	let mut letter_cnt: LetterMap<u32> = Default::default();
+	for &c in answer.iter() {
+		letter_cnt[c] += 1;
+	}
+
+	// pass 1: mark Correct
+	for i in 0..5usize {
+		if guess[i] == answer[i] {
+			letter_cnt[guess[i]] -= 1;
+			word_state[i] = Correct;
+		}
+	}
+
+	// pass 2: mark Occured or Redundant
+	for i in 0..5usize {
+		if guess[i] != answer[i] {
+			if letter_cnt[guess[i]] > 0 {
+				letter_cnt[guess[i]] -= 1;
+				word_state[i] = Occured;
+			} else {
+				word_state[i] = Redundant;
+			}
+		}
+	}
+
+	return word_state;
+}
+
+/// default maximum number of guesses in a round
+pub const MAX_GUESSES: u32 = 6;
+
 pub struct Plate {
-	goal:       Word,
-	letter_cnt: LetterMap<u32>,
-	keyboard:   LetterMap<LetterState>,
-	is_win:     bool,
-	history:    Vec<(Word, WordState)>,
-	difficult:  bool,
+	goal:        Word,
+	keyboard:    LetterMap<LetterState>,
+	is_win:      bool,
+	history:     Vec<(Word, WordState)>,
+	knowledge:   Knowledge,
+	difficulty:  Difficulty,
+	max_guesses: u32,
+	/// time taken by each guess, empty if untimed
+	durations:   Vec<Duration>,
+	is_time_up:  bool,
 }
 
 impl Default for LetterState {
@@ -48,6 +228,27 @@ impl Default for LetterState {
 }
 
 impl LetterState {
+	/// single letter code: G(reen), Y(ellow), R(ed) or X for unknown
+	pub fn to_char(&self) -> char {
+		match self {
+			Correct => 'G',
+			Occured => 'Y',
+			Redundant => 'R',
+			Unknown => 'X',
+		}
+	}
+
+	/// inverse of `to_char`, case insensitive
+	pub fn from_char(c: char) -> Option<LetterState> {
+		match c.to_ascii_uppercase() {
+			'G' => Some(Correct),
+			'Y' => Some(Occured),
+			'R' => Some(Redundant),
+			'X' => Some(Unknown),
+			_ => None,
+		}
+	}
+
 	fn or(lhs: LetterState, rhs: LetterState) -> LetterState {
 		match (lhs, rhs) {
 			(Correct, _) => Correct,
@@ -63,19 +264,22 @@ impl LetterState {
 
 impl Plate {
 	/// new Plate with candidate `word`
-	pub fn new(word: &Word, difficult: bool) -> Plate {
-		let mut letter_cnt: LetterMap<u32> = Default::default();
-		for &c in word {
-			letter_cnt[c] += 1;
-		}
+	pub fn new(word: &Word, difficulty: Difficulty) -> Plate {
+		return Plate::with_max_guesses(word, difficulty, MAX_GUESSES);
+	}
 
+	/// new Plate with candidate `word`, allowing `max_guesses` guesses
+	pub fn with_max_guesses(word: &Word, difficulty: Difficulty, max_guesses: u32) -> Plate {
 		return Plate {
 			goal: word.clone(),
-			letter_cnt,
 			keyboard: Default::default(),
 			is_win: false,
 			history: vec![],
-			difficult,
+			knowledge: Default::default(),
+			difficulty,
+			max_guesses,
+			durations: vec![],
+			is_time_up: false,
 		};
 	}
 
@@ -97,87 +301,73 @@ impl Plate {
 		&self.history
 	}
 
-	/// all history words and wordstates
+	pub fn difficulty(&self) -> Difficulty {
+		self.difficulty
+	}
+
+	pub fn max_guesses(&self) -> u32 {
+		self.max_guesses
+	}
+
+	/// best state of each letter
 	pub fn keyboard(&self) -> &LetterMap<LetterState> {
 		&self.keyboard
 	}
 
-	fn is_compatible(&self, word: &Word) -> Result<()> {
-		for (prev_word, prev_state) in &self.history {
-			let mut word_cnt: LetterMap<u32> = Default::default();
-			for &c in word {
-				word_cnt[c] += 1;
-			}
+	/// constraints deduced from history
+	pub fn knowledge(&self) -> &Knowledge {
+		&self.knowledge
+	}
 
-			// pass 1: check Correct
-			for i in 0..5usize {
-				if prev_state[i] == Correct {
-					if word[i] != prev_word[i] {
-						return Err(anyhow!(
-							"{} is not compatible with previous {}",
-							word_to_str(word),
-							word_to_str(prev_word)
-						));
-					}
-					word_cnt[word[i]] -= 1;
-				}
-			}
+	/// whether no more guesses are allowed
+	pub fn is_over(&self) -> bool {
+		self.is_win || self.is_time_up || self.count() >= self.max_guesses
+	}
 
-			// pass 2: check Occur
-			for i in 0..5usize {
-				if prev_state[i] == Occured {
-					if word_cnt[prev_word[i]] == 0 {
-						return Err(anyhow!(
-							"{} is not compatible with previous {}",
-							word_to_str(word),
-							word_to_str(prev_word)
-						));
-					}
-					word_cnt[prev_word[i]] -= 1;
-				}
-			}
-		}
+	/// time taken by each guess of a timed game
+	pub fn durations(&self) -> &[Duration] {
+		&self.durations
+	}
 
-		return Ok(());
+	/// time taken by the whole timed game
+	pub fn duration(&self) -> Duration {
+		self.durations.iter().sum()
 	}
 
-	pub fn guess(&mut self, word: &Word) -> Result<()> {
-		if self.difficult {
-			self.is_compatible(word)?;
-		}
+	pub fn is_time_up(&self) -> bool {
+		self.is_time_up
+	}
 
-		let mut word_state: WordState = Default::default();
-		let mut letter_cnt = self.letter_cnt.clone();
+	/// countdown expired before a guess, the game is lost
+	pub fn time_up(&mut self) {
+		if !self.is_over() {
+			self.is_time_up = true;
+		}
+	}
 
-		let mut set_state = |index, state| {
-			word_state[index] = state;
-			self.keyboard[word[index]] = LetterState::or(self.keyboard[word[index]], state);
-		};
+	/// guess `word` after thinking for `elapsed`
+	pub fn guess_timed(&mut self, word: &Word, elapsed: Duration) -> Result<(), GuessError> {
+		self.guess(word)?;
+		self.durations.push(elapsed);
+		return Ok(());
+	}
 
-		// pass 1: mark Correct
-		for i in 0..5usize {
-			if word[i] == self.goal[i] {
-				letter_cnt[word[i]] -= 1;
-				set_state(i, Correct);
-			}
+	pub fn guess(&mut self, word: &Word) -> Result<(), GuessError> {
+		if self.is_over() {
+			return Err(GuessError::GameOver);
 		}
+		self.knowledge.check(word, self.difficulty)?;
 
-		// pass 2: mark Redundant
+		let word_state = evaluate(word, &self.goal);
 		for i in 0..5usize {
-			if word[i] != self.goal[i] {
-				if letter_cnt[word[i]] > 0 {
-					letter_cnt[word[i]] -= 1;
-					set_state(i, Occured);
-				} else {
-					set_state(i, Redundant);
-				}
-			}
+			self.keyboard[word[i]] = LetterState::or(self.keyboard[word[i]], word_state[i]);
 		}
 
-		if word_eq(word, &self.goal) {
+		if *word == self.goal {
 			self.is_win = true;
 		}
 
+		self.knowledge.add(word, &word_state);
 		self.history.push((word.clone(), word_state));
 		return Ok(());
 	}
@@ -0,0 +1,183 @@
+use {
+	crate::plate::*,
+	serde::{Deserialize, Serialize},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod net;
+
+/// Messages from a racer to the race server
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMsg {
+	Join {
+		name: String,
+	},
+	/// colour-only feedback of a guess, see `LetterState::to_char`
+	Guess {
+		state: String,
+	},
+}
+
+/// Messages from the race server to every racer of a room
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMsg {
+	/// the room waits for `needed` racers
+	Waiting {
+		players: Vec<String>,
+		needed:  usize,
+	},
+	/// the answer is the first word of `rand_words(list_final, seed, day)`
+	Start {
+		seed: u64,
+		day:  u32,
+	},
+	/// progress of all racers
+	Board {
+		players: Vec<Racer>,
+	},
+	Error {
+		message: String,
+	},
+}
+
+/// Colour-only progress of a racer
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Racer {
+	pub name:   String,
+	pub states: Vec<String>,
+	/// order of finishing, None while still playing
+	pub finish: Option<u32>,
+	/// disconnected before finishing
+	#[serde(default)]
+	pub left:   bool,
+}
+
+impl Racer {
+	pub fn is_win(&self) -> bool {
+		self.states.last().map(|s| s.as_str()) == Some("GGGGG")
+	}
+
+	pub fn is_over(&self) -> bool {
+		self.left || self.is_win() || self.states.len() as u32 >= MAX_GUESSES
+	}
+}
+
+/// encode feedback of a guess for `ClientMsg::Guess`
+pub fn state_to_str(state: &WordState) -> String {
+	state.iter().map(LetterState::to_char).collect()
+}
+
+/// racers ordered by result: winners by guesses then finishing order, then the rest
+pub fn ranking(players: &[Racer]) -> Vec<&Racer> {
+	let mut result: Vec<&Racer> = players.iter().collect();
+	result.sort_by_key(|r| (!r.is_win(), r.states.len(), r.finish.unwrap_or(u32::MAX)));
+	return result;
+}
+
+/// A race room: every racer plays the same answer
+pub struct Room {
+	seed:     u64,
+	day:      u32,
+	needed:   usize,
+	players:  Vec<Racer>,
+	started:  bool,
+	finished: u32,
+}
+
+impl Room {
+	pub fn new(seed: u64, day: u32, needed: usize) -> Self {
+		Self {
+			seed,
+			day,
+			needed: needed.max(1),
+			players: vec![],
+			started: false,
+			finished: 0,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.players.is_empty()
+	}
+
+	fn board(&self) -> ServerMsg {
+		ServerMsg::Board {
+			players: self.players.clone(),
+		}
+	}
+
+	/// add a racer, returns messages for every racer
+	pub fn join(&mut self, name: &str) -> Result<Vec<ServerMsg>, String> {
+		if self.started {
+			return Err("the race has already started".to_string());
+		}
+		if name.is_empty() || self.players.iter().any(|r| r.name == name) {
+			return Err(format!("name {:?} is not available", name));
+		}
+		self.players.push(Racer {
+			name:   name.to_string(),
+			states: vec![],
+			finish: None,
+			left:   false,
+		});
+
+		let mut msgs = vec![ServerMsg::Waiting {
+			players: self.players.iter().map(|r| r.name.clone()).collect(),
+			needed:  self.needed,
+		}];
+		if self.players.len() >= self.needed {
+			self.started = true;
+			msgs.push(ServerMsg::Start {
+				seed: self.seed,
+				day:  self.day,
+			});
+			msgs.push(self.board());
+		}
+		return Ok(msgs);
+	}
+
+	/// record feedback of a guess, returns messages for every racer
+	pub fn guess(&mut self, name: &str, state: &str) -> Result<Vec<ServerMsg>, String> {
+		if !self.started {
+			return Err("the race has not started".to_string());
+		}
+		if state.len() != 5 || !state.chars().all(|c| "GYR".contains(c)) {
+			return Err(format!("invalid feedback {:?}", state));
+		}
+		let racer = self
+			.players
+			.iter_mut()
+			.find(|r| r.name == name)
+			.ok_or(format!("{} is not in the race", name))?;
+		if racer.is_over() {
+			return Err(GuessError::GameOver.to_string());
+		}
+
+		racer.states.push(state.to_string());
+		if racer.is_over() {
+			self.finished += 1;
+			racer.finish = Some(self.finished);
+		}
+		return Ok(vec![self.board()]);
+	}
+
+	/// remove a waiting racer, or give up the race of a started one
+	pub fn leave(&mut self, name: &str) -> Vec<ServerMsg> {
+		if self.started {
+			return match self.players.iter_mut().find(|r| r.name == name) {
+				Some(racer) if !racer.is_over() => {
+					racer.left = true;
+					vec![self.board()]
+				}
+				_ => vec![],
+			};
+		}
+		self.players.retain(|r| r.name != name);
+		return vec![ServerMsg::Waiting {
+			players: self.players.iter().map(|r| r.name.clone()).collect(),
+			needed:  self.needed,
+		}];
+	}
+}
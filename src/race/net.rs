@@ -0,0 +1,331 @@
+use {
+	super::*,
+	anyhow::{anyhow, bail, Result},
+	std::{
+		collections::HashMap,
+		io,
+		net::{TcpListener, TcpStream},
+		sync::{
+			atomic::{AtomicBool, Ordering},
+			mpsc::{self, Receiver, Sender, TryRecvError},
+			Arc, Mutex,
+		},
+		thread,
+		time::Duration,
+	},
+	tungstenite::{
+		error::ProtocolError,
+		handshake::server::{ErrorResponse, Request, Response},
+		http::StatusCode,
+		stream::MaybeTlsStream,
+		Message, WebSocket,
+	},
+};
+
+/// how long a connection blocks on reading before checking outgoing messages
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+struct RoomEntry {
+	room:    Room,
+	/// (name, outgoing channel) of connected racers
+	senders: Vec<(String, Sender<ServerMsg>)>,
+}
+
+type Rooms = Arc<Mutex<HashMap<String, RoomEntry>>>;
+
+/// room name and settings from a `/race/{room}?seed=&day=&players=` path
+fn parse_room_path(path: &str, needed: usize) -> Option<(String, Room)> {
+	let (path, query) = path.split_once('?').unwrap_or((path, ""));
+	let name = path.strip_prefix("/race/")?;
+	if name.is_empty() || name.contains('/') {
+		return None;
+	}
+
+	let (mut seed, mut day, mut needed) = (rand::random::<u32>() as u64, 1, needed);
+	for (key, value) in query.split('&').filter_map(|kv| kv.split_once('=')) {
+		match key {
+			"seed" => seed = value.parse().ok()?,
+			"day" => day = value.parse().ok().filter(|&day| day >= 1)?,
+			"players" => needed = value.parse().ok()?,
+			_ => {}
+		}
+	}
+	return Some((name.to_string(), Room::new(seed, day, needed)));
+}
+
+fn is_timeout(e: &tungstenite::Error) -> bool {
+	matches!(e, tungstenite::Error::Io(e)
+		if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut)
+}
+
+fn send_msg<T: Serialize, S: io::Read + io::Write>(ws: &mut WebSocket<S>, msg: &T) -> Result<()> {
+	ws.send(Message::Text(serde_json::to_string(msg)?))?;
+	return Ok(());
+}
+
+fn broadcast(entry: &RoomEntry, msgs: Vec<ServerMsg>) {
+	for msg in msgs {
+		for (_, sender) in &entry.senders {
+			let _ = sender.send(msg.clone());
+		}
+	}
+}
+
+/// serve race rooms at `ws://{addr}/race/{room}`, by default `needed` racers start a room
+pub fn serve(listener: TcpListener, needed: usize) -> Result<()> {
+	let rooms: Rooms = Default::default();
+	for stream in listener.incoming() {
+		let stream = match stream {
+			Ok(stream) => stream,
+			Err(e) => {
+				eprintln!("failed to accept: {}", e);
+				continue;
+			}
+		};
+		let rooms = rooms.clone();
+		thread::spawn(move || {
+			if let Err(e) = handle_racer(stream, rooms, needed) {
+				eprintln!("race connection closed: {}", e);
+			}
+		});
+	}
+	return Ok(());
+}
+
+fn handle_racer(stream: TcpStream, rooms: Rooms, needed: usize) -> Result<()> {
+	let mut room = None;
+	// the error type is fixed by tungstenite
+	#[allow(clippy::result_large_err)]
+	let callback = |request: &Request, response: Response| {
+		room = parse_room_path(&request.uri().to_string(), needed);
+		match room {
+			Some(_) => Ok(response),
+			None => {
+				let mut error = ErrorResponse::new(Some("expected /race/{room}".to_string()));
+				*error.status_mut() = StatusCode::NOT_FOUND;
+				Err(error)
+			}
+		}
+	};
+	let mut ws = tungstenite::accept_hdr(stream, callback)
+		.map_err(|e| anyhow!("handshake failed: {}", e))?;
+	let (room_name, new_room) = room.unwrap();
+	ws.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+
+	let (sender, receiver) = mpsc::channel();
+	let mut name: Option<String> = None;
+	let mut new_room = Some(new_room);
+	let result = (|| -> Result<()> {
+		loop {
+			while let Ok(msg) = receiver.try_recv() {
+				send_msg(&mut ws, &msg)?;
+			}
+			let text = match ws.read() {
+				Ok(Message::Text(text)) => text,
+				Ok(Message::Close(_)) => return Ok(()),
+				Ok(_) => continue,
+				Err(e) if is_timeout(&e) => continue,
+				// racers often just exit once the race is over
+				Err(tungstenite::Error::Protocol(ProtocolError::ResetWithoutClosingHandshake)) => {
+					return Ok(())
+				}
+				Err(e) => return Err(e.into()),
+			};
+
+			let mut rooms = rooms.lock().unwrap();
+			let result = match (serde_json::from_str(&text), &name) {
+				(Err(e), _) => Err(e.to_string()),
+				// the room is only created by a successful join
+				(
+					Ok(ClientMsg::Join {
+						name: new_name,
+					}),
+					None,
+				) => {
+					let entry = rooms.entry(room_name.clone()).or_insert_with(|| RoomEntry {
+						room:    new_room.take().unwrap_or(Room::new(0, 1, needed)),
+						senders: vec![],
+					});
+					let result = entry.room.join(&new_name);
+					match result.is_ok() {
+						true => {
+							entry.senders.push((new_name.clone(), sender.clone()));
+							name = Some(new_name);
+						}
+						// keep the settings of the room for the next try
+						false if entry.senders.is_empty() => {
+							new_room = rooms.remove(&room_name).map(|e| e.room)
+						}
+						false => {}
+					}
+					result
+				}
+				(
+					Ok(ClientMsg::Join {
+						..
+					}),
+					Some(_),
+				) => Err("already joined".to_string()),
+				(
+					Ok(ClientMsg::Guess {
+						state,
+					}),
+					Some(name),
+				) => rooms.get_mut(&room_name).unwrap().room.guess(name, &state),
+				(
+					Ok(ClientMsg::Guess {
+						..
+					}),
+					None,
+				) => Err("join the race first".to_string()),
+			};
+			match result {
+				Ok(msgs) => broadcast(&rooms[&room_name], msgs),
+				Err(message) => {
+					let _ = sender.send(ServerMsg::Error {
+						message,
+					});
+				}
+			}
+		}
+	})();
+
+	let mut rooms = rooms.lock().unwrap();
+	if let (Some(name), Some(entry)) = (&name, rooms.get_mut(&room_name)) {
+		entry.senders.retain(|(n, _)| n != name);
+		let msgs = entry.room.leave(name);
+		broadcast(entry, msgs);
+		if entry.senders.is_empty() {
+			rooms.remove(&room_name);
+		}
+	}
+	return result;
+}
+
+/// Connection of a racer to a race server
+pub struct RaceClient {
+	sender: Sender<ClientMsg>,
+	seed:   u64,
+	day:    u32,
+	board:  Arc<Mutex<Vec<Racer>>>,
+	closed: Arc<AtomicBool>,
+}
+
+impl RaceClient {
+	/// join the race at `url` as `name` and block until it starts
+	/// `on_msg` is called with every server message, from a background thread once started
+	pub fn join(
+		url: &str,
+		name: &str,
+		mut on_msg: impl FnMut(ServerMsg) + Send + 'static,
+	) -> Result<Self> {
+		let (mut ws, _) = tungstenite::connect(url)?;
+		send_msg(
+			&mut ws,
+			&ClientMsg::Join {
+				name: name.to_string(),
+			},
+		)?;
+
+		let (seed, day) = loop {
+			let msg: ServerMsg = match ws.read()? {
+				Message::Text(text) => serde_json::from_str(&text)?,
+				Message::Close(_) => bail!("race server closed the connection"),
+				_ => continue,
+			};
+			match msg {
+				ServerMsg::Start {
+					seed,
+					day,
+				} => break (seed, day),
+				ServerMsg::Error {
+					message,
+				} => bail!("cannot join the race: {}", message),
+				msg => on_msg(msg),
+			}
+		};
+
+		if let MaybeTlsStream::Plain(stream) = ws.get_ref() {
+			stream.set_read_timeout(Some(POLL_INTERVAL))?;
+		}
+		let (sender, receiver) = mpsc::channel();
+		let board: Arc<Mutex<Vec<Racer>>> = Default::default();
+		let closed: Arc<AtomicBool> = Default::default();
+		let (board_, closed_) = (board.clone(), closed.clone());
+		thread::spawn(move || {
+			let _ = run_client(ws, receiver, |msg| {
+				if let ServerMsg::Board {
+					players,
+				} = &msg
+				{
+					*board_.lock().unwrap() = players.clone();
+				}
+				on_msg(msg);
+			});
+			closed_.store(true, Ordering::SeqCst);
+		});
+		return Ok(Self {
+			sender,
+			seed,
+			day,
+			board,
+			closed,
+		});
+	}
+
+	/// seed of the shared answer, see `rand_words`
+	pub fn seed(&self) -> u64 {
+		self.seed
+	}
+
+	/// day of the shared answer, see `rand_words`
+	pub fn day(&self) -> u32 {
+		self.day
+	}
+
+	/// latest progress of all racers
+	pub fn board(&self) -> Vec<Racer> {
+		self.board.lock().unwrap().clone()
+	}
+
+	/// whether every racer is over or the connection is closed
+	pub fn is_finished(&self) -> bool {
+		let board = self.board.lock().unwrap();
+		return self.closed.load(Ordering::SeqCst)
+			|| (!board.is_empty() && board.iter().all(Racer::is_over));
+	}
+
+	/// report colour-only feedback of a guess to other racers
+	pub fn send_guess(&self, state: &WordState) -> Result<()> {
+		self.sender.send(ClientMsg::Guess {
+			state: state_to_str(state),
+		})?;
+		return Ok(());
+	}
+}
+
+fn run_client<S: io::Read + io::Write>(
+	mut ws: WebSocket<S>,
+	receiver: Receiver<ClientMsg>,
+	mut on_msg: impl FnMut(ServerMsg),
+) -> Result<()> {
+	loop {
+		loop {
+			match receiver.try_recv() {
+				Ok(msg) => send_msg(&mut ws, &msg)?,
+				Err(TryRecvError::Empty) => break,
+				Err(TryRecvError::Disconnected) => {
+					ws.close(None)?;
+					return Ok(());
+				}
+			}
+		}
+		match ws.read() {
+			Ok(Message::Text(text)) => on_msg(serde_json::from_str(&text)?),
+			Ok(Message::Close(_)) => return Ok(()),
+			Ok(_) => {}
+			Err(e) if is_timeout(&e) => {}
+			Err(e) => return Err(e.into()),
+		}
+	}
+}
@@ -0,0 +1,464 @@
+use {
+	crate::{config::ConfigError, plate::*, statistic::Statistic, word_gen::rand_words},
+	rand::seq::SliceRandom,
+	serde::{Deserialize, Serialize},
+	std::{
+		collections::{HashMap, HashSet},
+		io,
+		path::{Path, PathBuf},
+		time::{Duration, Instant},
+	},
+};
+
+/// finished games are kept this long for clients to fetch the result
+pub const FINISHED_TTL: Duration = Duration::from_secs(10 * 60);
+/// games in progress untouched this long are dropped from memory
+/// they are reloaded from the state directory, if any, when used again
+pub const IDLE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Options of a new game, mirroring the command line arguments
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct NewGame {
+	pub user:      Option<String>,
+	/// selected answer, conflicts with seed and day
+	pub word:      Option<String>,
+	pub seed:      Option<u64>,
+	pub day:       Option<u32>,
+	pub difficult: bool,
+	pub strict:    bool,
+}
+
+#[derive(Deserialize)]
+struct NewGuess {
+	word: String,
+}
+
+#[derive(Serialize)]
+pub struct GuessView {
+	pub word:  String,
+	/// letter codes, see `LetterState::to_char`
+	pub state: String,
+}
+
+#[derive(Serialize)]
+pub struct GameView {
+	pub id:       u64,
+	pub user:     String,
+	pub guesses:  Vec<GuessView>,
+	/// letter codes of A to Z
+	pub keyboard: String,
+	pub over:     bool,
+	pub win:      bool,
+	/// revealed when the game is over
+	pub answer:   Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct StatsView {
+	pub user:             String,
+	pub success:          u64,
+	pub fail:             u64,
+	pub average_attempts: f64,
+	pub top_words:        Vec<(String, u64)>,
+}
+
+/// Reasons for rejecting a request
+#[derive(Debug)]
+pub enum ApiError {
+	NotFound,
+	BadRequest(String),
+	Guess(GuessError),
+	Internal(String),
+}
+
+impl ApiError {
+	fn status(&self) -> u16 {
+		match self {
+			ApiError::NotFound => 404,
+			ApiError::BadRequest(_) => 400,
+			ApiError::Guess(_) => 422,
+			ApiError::Internal(_) => 500,
+		}
+	}
+
+	/// machine readable error kind
+	fn kind(&self) -> &'static str {
+		match self {
+			ApiError::NotFound => "not_found",
+			ApiError::BadRequest(_) => "bad_request",
+			ApiError::Guess(GuessError::Invalid(..)) => "invalid_word",
+			ApiError::Guess(GuessError::NotAcceptable(_)) => "not_acceptable",
+			ApiError::Guess(GuessError::GameOver) => "game_over",
+			ApiError::Guess(_) => "difficult_mode",
+			ApiError::Internal(_) => "internal",
+		}
+	}
+
+	fn message(&self) -> String {
+		match self {
+			ApiError::NotFound => "not found".to_string(),
+			ApiError::BadRequest(msg) | ApiError::Internal(msg) => msg.clone(),
+			ApiError::Guess(e) => e.to_string(),
+		}
+	}
+}
+
+/// HTTP status and JSON body
+pub struct Response {
+	pub status: u16,
+	pub body:   String,
+}
+
+/// A game as stored in games/{id}.json of the state directory, replayed when loaded
+#[derive(Clone, Serialize, Deserialize)]
+struct GameState {
+	user:      String,
+	answer:    String,
+	difficult: bool,
+	strict:    bool,
+	guesses:   Vec<String>,
+}
+
+impl GameState {
+	/// a plate with every guess applied
+	fn replay(&self) -> Result<Plate, GuessError> {
+		let word = |s: &String| word_from_str(s).map_err(|e| GuessError::Invalid(s.clone(), e));
+		let mut plate = Plate::new(
+			&word(&self.answer)?,
+			Difficulty::from_flags(self.difficult, self.strict),
+		);
+		for guess in &self.guesses {
+			plate.guess(&word(guess)?)?;
+		}
+		return Ok(plate);
+	}
+}
+
+struct Game {
+	state: GameState,
+	plate: Plate,
+	/// last request on the game
+	used:  Instant,
+}
+
+fn game_path(dir: &Path, id: u64) -> PathBuf {
+	dir.join("games").join(format!("{}.json", id))
+}
+
+fn read_game(path: &Path) -> io::Result<Game> {
+	let state: GameState = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+	let plate = state
+		.replay()
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+	return Ok(Game {
+		state,
+		plate,
+		used: Instant::now(),
+	});
+}
+
+fn write_game(dir: &Path, id: u64, state: &GameState) -> io::Result<()> {
+	std::fs::create_dir_all(dir.join("games"))?;
+	return std::fs::write(game_path(dir, id), serde_json::to_string(state)?);
+}
+
+/// Games and per-user statistics, kept in memory and in the state directory if any
+/// finished and idle games are dropped from memory, see `prune`
+pub struct GameServer {
+	set_acceptable: HashSet<Word>,
+	list_final:     Vec<Word>,
+	/// directory of per-user state files and games/{id}.json of games
+	state_dir:      Option<PathBuf>,
+	games:          HashMap<u64, Game>,
+	stats:          HashMap<String, Statistic>,
+	next_id:        u64,
+}
+
+fn parse_body<'a, T: Deserialize<'a>>(body: &'a str) -> Result<T, ApiError> {
+	serde_json::from_str(body).map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, ApiError> {
+	serde_json::to_string(value).map_err(|e| ApiError::Internal(e.to_string()))
+}
+
+fn parse_id(id: &str) -> Result<u64, ApiError> {
+	id.parse().map_err(|_| ApiError::NotFound)
+}
+
+/// user names double as state file names
+fn check_user(user: &str) -> Result<(), ApiError> {
+	match !user.is_empty()
+		&& user
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+	{
+		true => Ok(()),
+		false => Err(ApiError::BadRequest(format!(
+			"invalid user name {:?}",
+			user
+		))),
+	}
+}
+
+impl GameServer {
+	/// load the games stored in `state_dir`
+	pub fn new(
+		set_acceptable: HashSet<Word>,
+		list_final: Vec<Word>,
+		state_dir: Option<PathBuf>,
+	) -> io::Result<Self> {
+		let mut server = Self {
+			set_acceptable,
+			list_final,
+			state_dir: state_dir.clone(),
+			games: Default::default(),
+			stats: Default::default(),
+			next_id: 1,
+		};
+		let games = match state_dir {
+			Some(dir) if dir.join("games").is_dir() => dir.join("games"),
+			_ => return Ok(server),
+		};
+		for entry in std::fs::read_dir(games)? {
+			let path = entry?.path();
+			let id = match path
+				.file_stem()
+				.and_then(|s| s.to_str()?.parse::<u64>().ok())
+			{
+				Some(id) => id,
+				None => continue,
+			};
+			let game = read_game(&path)
+				.map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+			server.games.insert(id, game);
+			server.next_id = server.next_id.max(id + 1);
+		}
+		return Ok(server);
+	}
+
+	/// drop games finished for FINISHED_TTL, with their files, and games idle for IDLE_TTL
+	pub fn prune(&mut self, now: Instant) {
+		let state_dir = &self.state_dir;
+		self.games.retain(|id, game| {
+			let over = game.plate.is_over();
+			let ttl = match over {
+				true => FINISHED_TTL,
+				false => IDLE_TTL,
+			};
+			if now.saturating_duration_since(game.used) < ttl {
+				return true;
+			}
+			if let (true, Some(dir)) = (over, state_dir) {
+				// the result is in the statistic of the user, a file left behind is
+				// loaded as a finished game on restart and pruned again
+				let _ = std::fs::remove_file(game_path(dir, *id));
+			}
+			return false;
+		});
+	}
+
+	/// route a request to its handler
+	pub fn handle(&mut self, method: &str, url: &str, body: &str) -> Response {
+		self.prune(Instant::now());
+		let path: Vec<&str> = url
+			.split('?')
+			.next()
+			.unwrap_or("")
+			.split('/')
+			.filter(|s| !s.is_empty())
+			.collect();
+		let result = match (method, path.as_slice()) {
+			("POST", ["games"]) => self.create_game(body).map(|r| (201, r)),
+			("GET", ["games", id]) => parse_id(id)
+				.and_then(|id| self.game_view(id))
+				.map(|r| (200, r)),
+			("POST", ["games", id, "guesses"]) => parse_id(id)
+				.and_then(|id| self.guess(id, body))
+				.map(|r| (200, r)),
+			("GET", ["users", user, "stats"]) => self.user_stats(user).map(|r| (200, r)),
+			_ => Err(ApiError::NotFound),
+		};
+		return match result {
+			Ok((status, body)) => Response {
+				status,
+				body,
+			},
+			Err(e) => Response {
+				status: e.status(),
+				body:   serde_json::json!({ "error": e.kind(), "message": e.message() })
+					.to_string(),
+			},
+		};
+	}
+
+	/// game `id`, loaded from the state directory if it was dropped from memory
+	fn game(&mut self, id: u64) -> Result<&mut Game, ApiError> {
+		if !self.games.contains_key(&id) {
+			let dir = self.state_dir.as_ref().ok_or(ApiError::NotFound)?;
+			let game = match read_game(&game_path(dir, id)) {
+				Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(ApiError::NotFound),
+				result => result.map_err(|e| ApiError::Internal(e.to_string()))?,
+			};
+			self.games.insert(id, game);
+		}
+		let game = self.games.get_mut(&id).unwrap();
+		game.used = Instant::now();
+		return Ok(game);
+	}
+
+	fn game_view(&mut self, id: u64) -> Result<String, ApiError> {
+		let game = self.game(id)?;
+		let plate = &game.plate;
+		return to_json(&GameView {
+			id,
+			user: game.state.user.clone(),
+			guesses: plate
+				.history()
+				.iter()
+				.map(|(word, state)| GuessView {
+					word:  word_to_str(word),
+					state: state.iter().map(LetterState::to_char).collect(),
+				})
+				.collect(),
+			keyboard: plate
+				.keyboard()
+				.as_arr()
+				.iter()
+				.map(LetterState::to_char)
+				.collect(),
+			over: plate.is_over(),
+			win: plate.is_win(),
+			answer: plate.is_over().then(|| word_to_str(plate.goal())),
+		});
+	}
+
+	fn create_game(&mut self, body: &str) -> Result<String, ApiError> {
+		let options: NewGame = parse_body(body)?;
+		let user = options.user.unwrap_or("anonymous".to_string());
+		check_user(&user)?;
+
+		let goal = match (options.word, options.seed, options.day) {
+			(Some(word_str), None, None) => {
+				let word = word_from_str(&word_str).map_err(|e| {
+					ApiError::BadRequest(ConfigError::InvalidSelect(word_str, e).to_string())
+				})?;
+				if !self.set_acceptable.contains(&word) {
+					return Err(ApiError::Guess(GuessError::NotAcceptable(word)));
+				}
+				word
+			}
+			(Some(_), _, _) => {
+				return Err(ApiError::BadRequest(
+					ConfigError::Conflict("word", "seed/day").to_string(),
+				))
+			}
+			(None, None, None) => *self
+				.list_final
+				.choose(&mut rand::thread_rng())
+				.ok_or(ApiError::Internal("empty final list".to_string()))?,
+			(None, seed, day) => {
+				let day = day.unwrap_or(1);
+				if day == 0 || day as usize > self.list_final.len() {
+					return Err(ApiError::BadRequest(format!(
+						"day {} is out of the final list",
+						day
+					)));
+				}
+				rand_words(&self.list_final, seed.unwrap_or(0), day)()
+					.map_err(|e| ApiError::BadRequest(e.to_string()))?
+			}
+		};
+
+		let state = GameState {
+			user,
+			answer: word_to_str(&goal),
+			difficult: options.difficult,
+			strict: options.strict,
+			guesses: vec![],
+		};
+		let plate = state.replay().map_err(ApiError::Guess)?;
+		let id = self.next_id;
+		if let Some(dir) = &self.state_dir {
+			write_game(dir, id, &state).map_err(|e| ApiError::Internal(e.to_string()))?;
+		}
+		self.next_id += 1;
+		self.games.insert(
+			id,
+			Game {
+				state,
+				plate,
+				used: Instant::now(),
+			},
+		);
+		return self.game_view(id);
+	}
+
+	fn guess(&mut self, id: u64, body: &str) -> Result<String, ApiError> {
+		let guess: NewGuess = parse_body(body)?;
+		let word = word_from_str(&guess.word)
+			.map_err(|e| ApiError::Guess(GuessError::Invalid(guess.word.clone(), e)))?;
+		if !self.set_acceptable.contains(&word) {
+			return Err(ApiError::Guess(GuessError::NotAcceptable(word)));
+		}
+
+		// files are written first, so a failed write leaves the game and statistic unchanged
+		let mut state = self.game(id)?.state.clone();
+		state.guesses.push(word_to_str(&word));
+		let plate = state.replay().map_err(ApiError::Guess)?;
+		let statistic = match plate.is_over() {
+			false => None,
+			true => {
+				self.load_stats(&state.user)?;
+				let mut statistic = self.stats[&state.user].clone();
+				statistic.add_plate(&plate);
+				Some(statistic)
+			}
+		};
+		if let Some(dir) = &self.state_dir {
+			write_game(dir, id, &state).map_err(|e| ApiError::Internal(e.to_string()))?;
+			if let Some(statistic) = &statistic {
+				let path = dir.join(format!("{}.json", state.user));
+				if let Err(e) = statistic.store_to_file(&path) {
+					// take the guess back, so it can be sent again
+					let _ = write_game(dir, id, &self.games[&id].state);
+					return Err(ApiError::Internal(e.to_string()));
+				}
+			}
+		}
+		if let Some(statistic) = statistic {
+			self.stats.insert(state.user.clone(), statistic);
+		}
+		let game = self.games.get_mut(&id).unwrap();
+		game.state = state;
+		game.plate = plate;
+		return self.game_view(id);
+	}
+
+	/// load statistic of `user` from its state file on first use
+	fn load_stats(&mut self, user: &str) -> Result<(), ApiError> {
+		if !self.stats.contains_key(user) {
+			let statistic = match &self.state_dir {
+				None => Statistic::new(),
+				Some(dir) => Statistic::load_from_file(&dir.join(format!("{}.json", user)))
+					.map_err(|e| ApiError::Internal(e.to_string()))?,
+			};
+			self.stats.insert(user.to_string(), statistic);
+		}
+		return Ok(());
+	}
+
+	fn user_stats(&mut self, user: &str) -> Result<String, ApiError> {
+		check_user(user)?;
+		self.load_stats(user)?;
+		let s = &self.stats[user];
+		return to_json(&StatsView {
+			user:             user.to_string(),
+			success:          s.success_cnt(),
+			fail:             s.fail_cnt(),
+			average_attempts: s.success_attempt_average(),
+			top_words:        s.top5_words().map(|x| (x.str.clone(), x.cnt)).collect(),
+		});
+	}
+}
@@ -1,15 +1,16 @@
 use {
-	crate::plate::*,
-	anyhow::Result,
-	serde::{Deserialize, Serialize},
+	crate::{achievement::Achievement, analysis::Analysis, plate::*},
+	anyhow::{bail, Result},
+	serde::{de::IgnoredAny, Deserialize, Serialize},
 	serde_json::{from_str, to_string},
 	std::{
 		collections::{BTreeMap, BTreeSet},
 		path::Path,
+		time::Duration,
 	},
 };
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct WordCnt {
 	pub str: String,
 	pub cnt: u64,
@@ -25,27 +26,59 @@ impl Ord for WordCnt {
 	}
 }
 
+/// A finished game as recorded in the state file
 #[derive(Clone, Serialize, Deserialize)]
-struct StatisticGame {
-	answer:  String,
-	guesses: Vec<String>,
+pub struct StatisticGame {
+	pub answer:    String,
+	pub guesses:   Vec<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub analysis:  Option<Analysis>,
+	/// milliseconds taken by each guess of a timed game
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub durations: Option<Vec<u64>>,
+	/// day of a daily puzzle replayed from the archive, not counted for streaks
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub archive:   Option<u32>,
+}
+
+impl StatisticGame {
+	pub fn is_win(&self) -> bool {
+		self.guesses.last() == Some(&self.answer)
+	}
+
+	/// time taken by the whole game, if timed
+	pub fn duration(&self) -> Option<Duration> {
+		let ms: u64 = self.durations.as_ref()?.iter().sum();
+		return Some(Duration::from_millis(ms));
+	}
 }
 
 #[derive(Serialize, Deserialize)]
 struct StatisticJSON {
 	total_rounds: Option<u64>,
 	games:        Option<Vec<StatisticGame>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	achievements: Option<BTreeSet<Achievement>>,
+	/// only in hot-seat state files, which must not be overwritten by a single player
+	#[serde(default, skip_serializing)]
+	players:      Option<IgnoredAny>,
 }
 
-#[derive(Default)]
+#[derive(Serialize, Deserialize)]
+struct PlayerStatisticJSON {
+	players: BTreeMap<String, StatisticJSON>,
+}
+
+#[derive(Default, Clone)]
 pub struct Statistic {
 	success_cnt:        u64,
 	fail_cnt:           u64,
 	success_attemp_cnt: u64,
 
-	word_cnt:  BTreeMap<String, u64>,
-	top_words: BTreeSet<WordCnt>,
-	games:     Vec<StatisticGame>,
+	word_cnt:     BTreeMap<String, u64>,
+	top_words:    BTreeSet<WordCnt>,
+	games:        Vec<StatisticGame>,
+	achievements: BTreeSet<Achievement>,
 }
 
 impl Statistic {
@@ -57,21 +90,33 @@ impl Statistic {
 			word_cnt:           Default::default(),
 			top_words:          Default::default(),
 			games:              Default::default(),
+			achievements:       Default::default(),
+		}
+	}
+
+	fn from_state(state: StatisticJSON) -> Self {
+		let mut result: Statistic = Default::default();
+		for game in state.games.unwrap_or_default() {
+			result._add_game(game);
+		}
+		result.achievements = state.achievements.unwrap_or_default();
+		return result;
+	}
+	fn to_state(&self) -> StatisticJSON {
+		StatisticJSON {
+			total_rounds: Some(self.success_cnt + self.fail_cnt),
+			games:        Some(self.games.clone()),
+			achievements: (!self.achievements.is_empty()).then(|| self.achievements.clone()),
+			players:      None,
 		}
 	}
 
 	pub fn load_from_json(json_str: &str) -> Result<Self> {
 		let state: StatisticJSON = from_str(json_str)?;
-		return match state.games {
-			None => Ok(Default::default()),
-			Some(games) => {
-				let mut result: Statistic = Default::default();
-				for game in games {
-					result._add_plate(game.answer, game.guesses);
-				}
-				return Ok(result);
-			}
-		};
+		if state.players.is_some() {
+			bail!("this is a hot-seat state file, use --players");
+		}
+		return Ok(Self::from_state(state));
 	}
 	pub fn load_from_file(path: &Path) -> Result<Self> {
 		if !path.exists() {
@@ -81,14 +126,10 @@ impl Statistic {
 	}
 
 	pub fn store_to_json(&self) -> String {
-		to_string(&StatisticJSON {
-			total_rounds: Some(self.success_cnt + self.fail_cnt),
-			games:        Some(self.games.clone()),
-		})
-		.unwrap()
+		to_string(&self.to_state()).unwrap()
 	}
 	pub fn store_to_file(&self, path: &Path) -> Result<()> {
-		std::fs::write(path, &self.store_to_json())?;
+		std::fs::write(path, self.store_to_json())?;
 		return Ok(());
 	}
 
@@ -132,39 +173,215 @@ impl Statistic {
 		}
 	}
 
-	fn _add_plate(&mut self, goal: String, history: Vec<String>) {
-		// is_win?
-		match goal == *history.last().unwrap() {
+	fn _add_game(&mut self, game: StatisticGame) {
+		match game.is_win() {
 			true => {
 				self.success_cnt += 1;
-				self.success_attemp_cnt += history.len() as u64;
+				self.success_attemp_cnt += game.guesses.len() as u64;
 			}
 			false => {
 				self.fail_cnt += 1;
 			}
 		};
-		for word in &history {
+		for word in &game.guesses {
 			self.add_word(word);
 		}
-		self.games.push(StatisticGame {
-			answer:  goal,
-			guesses: history,
-		});
+		self.games.push(game);
 	}
-	
+
 	/// update statistic by a WHOLE plate
 	pub fn add_plate(&mut self, plate: &Plate) {
-		self._add_plate(
-			word_to_str(plate.goal()),
-			plate
+		self._add_game(StatisticGame {
+			answer:    word_to_str(plate.goal()),
+			guesses:   plate
 				.history()
 				.iter()
 				.map(|(w, _)| word_to_str(w))
 				.collect(),
-		)
+			analysis:  None,
+			durations: (!plate.durations().is_empty()).then(|| {
+				plate
+					.durations()
+					.iter()
+					.map(|d| d.as_millis() as u64)
+					.collect()
+			}),
+			archive:   None,
+		})
+	}
+
+	/// mark the last game as `day` replayed from the archive
+	pub fn mark_archived(&mut self, day: u32) {
+		self.games.last_mut().unwrap().archive = Some(day);
+	}
+
+	/// update statistic by a WHOLE plate, keeping its analysis
+	pub fn add_analyzed_plate(&mut self, plate: &Plate, analysis: Analysis) {
+		self.add_plate(plate);
+		self.games.last_mut().unwrap().analysis = Some(analysis);
+	}
+
+	/// add the games of `other` not found in this one, and its achievements
+	/// e.g. to combine the history of the terminal and the browser
+	/// a game played n times is kept n times, only games already here are skipped
+	/// returns the number of games added
+	pub fn merge(&mut self, other: Statistic) -> usize {
+		let key = |g: &StatisticGame| {
+			(
+				g.answer.clone(),
+				g.guesses.clone(),
+				g.durations.clone(),
+				g.archive,
+			)
+		};
+		// times each game is already here, consumed by the same games of `other`
+		let mut existing: BTreeMap<_, usize> = BTreeMap::new();
+		for game in &self.games {
+			*existing.entry(key(game)).or_default() += 1;
+		}
+		let mut added = 0;
+		for game in other.games {
+			match existing.get_mut(&key(&game)) {
+				Some(n) if *n > 0 => *n -= 1,
+				_ => {
+					self._add_game(game);
+					added += 1;
+				}
+			}
+		}
+		self.achievements.extend(other.achievements);
+		return added;
 	}
 
 	pub fn top5_words(&self) -> impl Iterator<Item = &WordCnt> {
 		self.top_words.iter().take(5)
 	}
+
+	/// all games in the order they were played
+	pub fn games(&self) -> &[StatisticGame] {
+		&self.games
+	}
+	/// average time of timed games
+	pub fn average_time(&self) -> Option<Duration> {
+		let times: Vec<Duration> = self.games.iter().filter_map(|g| g.duration()).collect();
+		return match times.len() {
+			0 => None,
+			n => Some(times.iter().sum::<Duration>() / n as u32),
+		};
+	}
+	/// shortest time of timed wins
+	pub fn fastest_time(&self) -> Option<Duration> {
+		self.games
+			.iter()
+			.filter(|g| g.is_win())
+			.filter_map(|g| g.duration())
+			.min()
+	}
+	/// average time of a single guess in timed games
+	pub fn average_guess_time(&self) -> Option<Duration> {
+		let times: Vec<u64> = self
+			.games
+			.iter()
+			.filter_map(|g| g.durations.as_ref())
+			.flatten()
+			.copied()
+			.collect();
+		return match times.len() {
+			0 => None,
+			n => Some(Duration::from_millis(times.iter().sum::<u64>() / n as u64)),
+		};
+	}
+
+	/// achievements unlocked so far
+	pub fn achievements(&self) -> &BTreeSet<Achievement> {
+		&self.achievements
+	}
+	/// unlock achievements met by `plate`, which must be the last game added
+	/// returns the newly unlocked ones
+	pub fn unlock_achievements(&mut self, plate: &Plate) -> Vec<Achievement> {
+		let new: Vec<Achievement> = Achievement::ALL
+			.into_iter()
+			.filter(|a| !self.achievements.contains(a) && a.is_met(plate, self))
+			.collect();
+		self.achievements.extend(&new);
+		return new;
+	}
+
+	/// games counted for streaks, i.e. not replayed from the archive
+	fn streak_games(&self) -> impl DoubleEndedIterator<Item = &StatisticGame> {
+		self.games.iter().filter(|g| g.archive.is_none())
+	}
+	/// wins in a row up to the last game
+	pub fn current_streak(&self) -> u64 {
+		self.streak_games().rev().take_while(|g| g.is_win()).count() as u64
+	}
+	/// most wins in a row
+	pub fn max_streak(&self) -> u64 {
+		let mut result = 0;
+		let mut streak = 0;
+		for game in self.streak_games() {
+			streak = match game.is_win() {
+				true => streak + 1,
+				false => 0,
+			};
+			result = result.max(streak);
+		}
+		return result;
+	}
+}
+
+/// Statistic of each player in hot-seat mode, stored in one state file
+#[derive(Default)]
+pub struct PlayerStatistic {
+	players: BTreeMap<String, Statistic>,
+}
+
+impl PlayerStatistic {
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	pub fn load_from_json(json_str: &str) -> Result<Self> {
+		let state: PlayerStatisticJSON = from_str(json_str)?;
+		return Ok(Self {
+			players: state
+				.players
+				.into_iter()
+				.map(|(name, state)| (name, Statistic::from_state(state)))
+				.collect(),
+		});
+	}
+	pub fn load_from_file(path: &Path) -> Result<Self> {
+		if !path.exists() {
+			return Ok(Self::new());
+		}
+		return Self::load_from_json(&std::fs::read_to_string(path)?);
+	}
+
+	pub fn store_to_json(&self) -> String {
+		to_string(&PlayerStatisticJSON {
+			players: self
+				.players
+				.iter()
+				.map(|(name, s)| (name.clone(), s.to_state()))
+				.collect(),
+		})
+		.unwrap()
+	}
+	pub fn store_to_file(&self, path: &Path) -> Result<()> {
+		std::fs::write(path, self.store_to_json())?;
+		return Ok(());
+	}
+
+	pub fn get(&self, name: &str) -> Option<&Statistic> {
+		self.players.get(name)
+	}
+	/// statistic of `name`, created on first use
+	pub fn player_mut(&mut self, name: &str) -> &mut Statistic {
+		self.players.entry(name.to_string()).or_default()
+	}
+	/// (name, statistic) of every player ordered by name
+	pub fn iter(&self) -> impl Iterator<Item = (&String, &Statistic)> {
+		self.players.iter()
+	}
 }
@@ -4,7 +4,7 @@ use {
 };
 
 /// Specialized map for 26 letters
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct LetterMap<T> {
 	_data: [T; 26],
 }
@@ -20,20 +20,45 @@ where
 	}
 }
 
+/// offset of an uppercase letter, None for anything else
+fn letter_offset(c: char) -> Option<usize> {
+	match c {
+		'A'..='Z' => Some((c as usize) - ('A' as usize)),
+		_ => None,
+	}
+}
+
 impl<T> Index<char> for LetterMap<T> {
 	type Output = T;
 	fn index(&self, index: char) -> &Self::Output {
-		&self._data[(index as usize) - 65]
+		self.get(index)
+			.unwrap_or_else(|| panic!("LetterMap: {:?} is not an uppercase letter", index))
 	}
 }
 
 impl<T> IndexMut<char> for LetterMap<T> {
 	fn index_mut(&mut self, index: char) -> &mut Self::Output {
-		&mut self._data[(index as usize) - 65]
+		self.get_mut(index)
+			.unwrap_or_else(|| panic!("LetterMap: {:?} is not an uppercase letter", index))
+	}
+}
+
+impl<T: Copy> LetterMap<T> {
+	/// map with every letter set to `value`
+	pub fn filled(value: T) -> Self {
+		Self {
+			_data: [value; 26]
+		}
 	}
 }
 
 impl<T> LetterMap<T> {
+	pub fn get(&self, c: char) -> Option<&T> {
+		Some(&self._data[letter_offset(c)?])
+	}
+	pub fn get_mut(&mut self, c: char) -> Option<&mut T> {
+		Some(&mut self._data[letter_offset(c)?])
+	}
 	pub fn as_arr(&self) -> &[T; 26] {
 		&self._data
 	}
@@ -8,12 +8,12 @@ use {
 /// RepeatReader implies Iterator trait for word reading
 /// It takes a FnMut as word getter (e.g. reader/rander)
 /// Wrap it with reading Y/N expect the first round
-pub struct RepeatReader<F: FnMut() -> Result<Word>> {
+pub struct RepeatReader<T, F: FnMut() -> Result<T>> {
 	first_time: bool,
 	reader:     F,
 }
 
-impl<F: FnMut() -> Result<Word>> RepeatReader<F> {
+impl<T, F: FnMut() -> Result<T>> RepeatReader<T, F> {
 	pub fn new(reader: F) -> Self {
 		Self {
 			first_time: true,
@@ -22,8 +22,8 @@ impl<F: FnMut() -> Result<Word>> RepeatReader<F> {
 	}
 }
 
-impl<F: FnMut() -> Result<Word>> Iterator for RepeatReader<F> {
-	type Item = Word;
+impl<T, F: FnMut() -> Result<T>> Iterator for RepeatReader<T, F> {
+	type Item = T;
 	fn next(&mut self) -> Option<Self::Item> {
 		let is_next = match self.first_time {
 			true => {
@@ -52,18 +52,30 @@ pub fn reader_from_set<'a>(
 		return if set.contains(&word) {
 			Ok(word)
 		} else {
-			Err(anyhow!("word {} out of range", word_to_str(&word)))
+			Err(GuessError::NotAcceptable(word).into())
 		};
 	}
 }
 
 /// iter-like rand word getter
 /// returns a FnMut, which returns next random word on each call
+/// days count from 1, every call fails for day 0
 pub fn rand_words(list: &Vec<Word>, seed: u64, date: u32) -> impl FnMut() -> Result<Word> {
 	use rand::seq::SliceRandom;
 	let mut list = list.clone();
 	let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
 	list.shuffle(&mut rng);
-	let mut iter = list.into_iter().skip((date - 1) as usize);
-	return move || iter.next().ok_or(anyhow!("End of random list"));
-}
\ No newline at end of file
+	let mut iter = list.into_iter().skip(date.saturating_sub(1) as usize);
+	return move || match date {
+		0 => Err(anyhow!("day must start from 1")),
+		_ => iter.next().ok_or(anyhow!("End of random list")),
+	};
+}
+
+/// answers of a round: one shared by all players, or `n` taken in turn
+pub fn round_words(
+	mut next_word: impl FnMut() -> Result<Word>,
+	n: usize,
+) -> impl FnMut() -> Result<Vec<Word>> {
+	move || (0..n).map(|_| next_word()).collect()
+}
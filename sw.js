@@ -0,0 +1,49 @@
+// Service worker of the installed app: every file is cached on install and served
+// from the cache first, so the game works offline. Word lists are built into the
+// wasm bundle, statistic and settings stay in localStorage.
+//
+// __VERSION__ and __FILES__ are filled in by build-web.sh, so `trunk serve` never
+// installs it and a new build replaces the old cache.
+const CACHE = "wordle-__VERSION__";
+const FILES = ["/", "__FILES__"];
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE)
+      .then((cache) => cache.addAll(FILES))
+      .then(() => self.skipWaiting()),
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) => Promise.all(keys.filter((key) => key !== CACHE).map((key) => caches.delete(key))))
+      .then(() => self.clients.claim()),
+  );
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  // race servers and anything else not ours go to the network
+  if (request.method !== "GET" || new URL(request.url).origin !== self.location.origin) {
+    return;
+  }
+  // /play, /settings, ... are all routes of index.html
+  const key = request.mode === "navigate" ? "/index.html" : request;
+  event.respondWith(
+    caches.match(key).then(
+      (cached) =>
+        cached ||
+        fetch(request).then((response) => {
+          if (response.ok) {
+            const copy = response.clone();
+            caches.open(CACHE).then((cache) => cache.put(key, copy));
+          }
+          return response;
+        }),
+    ),
+  );
+});
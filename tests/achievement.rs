@@ -0,0 +1,70 @@
+use wordle::{achievement::Achievement, plate::*, statistic::Statistic};
+
+fn play(answer: &str, guesses: &[&str], difficulty: Difficulty) -> Plate {
+	let mut plate = Plate::new(&word_from_str(answer).unwrap(), difficulty);
+	for guess in guesses {
+		plate.guess(&word_from_str(guess).unwrap()).unwrap();
+	}
+	return plate;
+}
+
+fn unlock(statistic: &mut Statistic, plate: &Plate) -> Vec<Achievement> {
+	statistic.add_plate(plate);
+	return statistic.unlock_achievements(plate);
+}
+
+#[test]
+fn test_first_guess() {
+	let mut statistic = Statistic::new();
+	let plate = play("CRANE", &["CRANE"], Difficulty::Normal);
+	assert_eq!(
+		unlock(&mut statistic, &plate),
+		vec![Achievement::FirstGuess, Achievement::NoGreyReuse]
+	);
+	// unlocked only once
+	assert_eq!(unlock(&mut statistic, &plate), vec![]);
+}
+
+#[test]
+fn test_grey_reuse() {
+	let mut statistic = Statistic::new();
+	// C of CRANE is absent from BUILD, then reused by CHILD
+	let plate = play("BUILD", &["CRANE", "CHILD", "BUILD"], Difficulty::Normal);
+	assert_eq!(unlock(&mut statistic, &plate), vec![]);
+	let plate = play("BUILD", &["CRANE", "GUILD", "BUILD"], Difficulty::Normal);
+	assert_eq!(
+		unlock(&mut statistic, &plate),
+		vec![Achievement::NoGreyReuse]
+	);
+}
+
+#[test]
+fn test_hard_last_guess() {
+	let guesses = ["CRANE", "BOXES", "BIKES", "BITES", "BILES", "BILLS"];
+	let mut statistic = Statistic::new();
+	let plate = play("BILLS", &guesses, Difficulty::Normal);
+	assert!(!unlock(&mut statistic, &plate).contains(&Achievement::HardLastGuess));
+	let plate = play("BILLS", &guesses, Difficulty::Hard);
+	assert!(unlock(&mut statistic, &plate).contains(&Achievement::HardLastGuess));
+}
+
+#[test]
+fn test_streak_and_games() {
+	let mut statistic = Statistic::new();
+	let win = play("BUILD", &["CRANE", "BUILD"], Difficulty::Normal);
+	let lose = play("BUILD", &["CRANE"; 6], Difficulty::Normal);
+	for _ in 0..9 {
+		unlock(&mut statistic, &win);
+	}
+	assert!(!statistic.achievements().contains(&Achievement::Streak10));
+	assert_eq!(unlock(&mut statistic, &win), vec![Achievement::Streak10]);
+
+	for _ in 10..99 {
+		unlock(&mut statistic, &lose);
+	}
+	assert_eq!(unlock(&mut statistic, &lose), vec![Achievement::Games100]);
+
+	// kept in the state file
+	let statistic = Statistic::load_from_json(&statistic.store_to_json()).unwrap();
+	assert_eq!(statistic.achievements().len(), 3);
+}
@@ -0,0 +1,106 @@
+use {
+	std::collections::HashSet,
+	wordle::{analysis::*, plate::*},
+};
+
+fn words(list: &[&str]) -> Vec<Word> {
+	list.iter().map(|s| word_from_str(s).unwrap()).collect()
+}
+
+#[test]
+fn test_expected_remaining() {
+	let candidates = words(&["build", "guild", "built", "quilt"]);
+	assert_eq!(expected_remaining(&words(&["build"])[0], &candidates), 1.0);
+	assert_eq!(expected_remaining(&words(&["quilt"])[0], &candidates), 1.5);
+	assert_eq!(expected_remaining(&words(&["crane"])[0], &candidates), 4.0);
+}
+
+#[test]
+fn test_analyze() {
+	let list_final = words(&["build", "guild", "built", "quilt"]);
+	let set_acceptable: HashSet<Word> = words(&["build", "guild", "built", "quilt", "crane"])
+		.into_iter()
+		.collect();
+
+	let mut plate = Plate::new(&list_final[0], Difficulty::Normal);
+	plate.guess(&list_final[3]).unwrap();
+	plate.guess(&list_final[0]).unwrap();
+	let analysis = analyze(&plate, &list_final, &set_acceptable, None);
+
+	let quilt = &analysis.guesses[0];
+	assert_eq!((quilt.before, quilt.after), (4, 2));
+	assert_eq!((quilt.expected, quilt.best_expected), (1.5, 1.0));
+	assert_eq!(quilt.best_guess, "BUILD");
+	assert!((quilt.skill - 100.0 * (4.0f64 / 1.5).log2() / 2.0).abs() < 1e-9);
+	assert!((quilt.luck - (1.5f64 / 2.0).log2()).abs() < 1e-9);
+
+	let build = &analysis.guesses[1];
+	assert_eq!((build.before, build.after), (2, 1));
+	assert_eq!((build.skill, build.luck), (100.0, 0.0));
+
+	assert!((analysis.skill - (quilt.skill + 100.0) / 2.0).abs() < 1e-9);
+	assert!((analysis.luck - quilt.luck).abs() < 1e-9);
+}
+
+#[test]
+fn test_answer_not_final() {
+	let list_final = words(&["build", "guild"]);
+	let set_acceptable: HashSet<Word> = words(&["build", "guild", "quilt", "crane"])
+		.into_iter()
+		.collect();
+
+	// no candidate is left after BUILD, nor before QUILT
+	let mut plate = Plate::new(&words(&["quilt"])[0], Difficulty::Normal);
+	plate.guess(&words(&["build"])[0]).unwrap();
+	plate.guess(&words(&["quilt"])[0]).unwrap();
+	let analysis = analyze(&plate, &list_final, &set_acceptable, None);
+	assert_eq!(analysis.guesses[1].before, 0);
+	for g in &analysis.guesses {
+		assert!(g.skill.is_finite() && g.luck.is_finite(), "{:?}", g);
+	}
+	assert!(analysis.skill.is_finite() && analysis.luck.is_finite());
+
+	// the state file can be read again
+	let json = serde_json::to_string(&analysis).unwrap();
+	assert!(!json.contains("null"), "{}", json);
+	assert_eq!(serde_json::from_str::<Analysis>(&json).unwrap(), analysis);
+}
+
+#[test]
+fn test_solve() {
+	let list_final = words(&["build", "guild", "built", "quilt"]);
+	let set_acceptable: HashSet<Word> = words(&["build", "guild", "built", "quilt", "crane"])
+		.into_iter()
+		.collect();
+
+	// BUILD splits the rest apart
+	let plate = solve(
+		&list_final[3],
+		&list_final,
+		&set_acceptable,
+		None,
+		Difficulty::Normal,
+	)
+	.unwrap();
+	assert!(plate.is_win());
+	assert_eq!(plate.history()[0].0, list_final[0]);
+	assert_eq!(plate.count(), 2);
+
+	// not in the final list, but acceptable
+	let crane = words(&["crane"])[0];
+	let plate = solve(&crane, &list_final, &set_acceptable, None, Difficulty::Hard).unwrap();
+	assert!(plate.is_win());
+
+	let slate = words(&["slate"])[0];
+	assert_eq!(
+		solve(
+			&slate,
+			&list_final,
+			&set_acceptable,
+			None,
+			Difficulty::Normal
+		)
+		.err(),
+		Some(GuessError::NotAcceptable(slate))
+	);
+}
@@ -0,0 +1,60 @@
+use wordle::{
+	archive::*, config::load_lists, plate::*, statistic::Statistic, word_gen::rand_words,
+};
+
+#[test]
+fn test_day_at() {
+	assert_eq!(day_at(DAY_ONE * 86400), 1);
+	assert_eq!(day_at(DAY_ONE * 86400 + 86399), 1);
+	assert_eq!(day_at((DAY_ONE + 30) * 86400), 31);
+	assert_eq!(day_at(0), 1);
+}
+
+#[test]
+fn test_archive() {
+	let (_, list_final) = load_lists(None, None).unwrap();
+	let statistic = Statistic::load_from_json(
+		r#"{"games":[
+			{"answer":"RARER","guesses":["CRANE","RARER"]},
+			{"answer":"BOWEL","guesses":["CRANE"]},
+			{"answer":"IDIOT","guesses":["CRANE"]},
+			{"answer":"IDIOT","guesses":["IDIOT"],"archive":5}
+		]}"#,
+	)
+	.unwrap();
+	let days = archive(&list_final, 0, 5, &statistic);
+	let status: Vec<DayStatus> = days.iter().map(|d| d.status).collect();
+	assert_eq!(
+		status,
+		vec![
+			DayStatus::Unplayed,
+			DayStatus::Won,
+			DayStatus::Played,
+			DayStatus::Unplayed,
+			DayStatus::Won
+		]
+	);
+	// answers in the order of rand_words
+	let mut next_word = rand_words(&list_final, 0, 1);
+	for d in &days {
+		assert_eq!(d.answer, next_word().unwrap());
+	}
+	assert_eq!(word_to_str(&days[0].answer), "VAGUE");
+}
+
+#[test]
+fn test_archive_streak() {
+	// archived games neither extend nor break streaks
+	let statistic = Statistic::load_from_json(
+		r#"{"games":[
+			{"answer":"RARER","guesses":["RARER"]},
+			{"answer":"BOWEL","guesses":["CRANE"],"archive":3},
+			{"answer":"MOOSE","guesses":["MOOSE"]},
+			{"answer":"IDIOT","guesses":["IDIOT"],"archive":5}
+		]}"#,
+	)
+	.unwrap();
+	assert_eq!(statistic.current_streak(), 2);
+	assert_eq!(statistic.max_streak(), 2);
+	assert_eq!(statistic.success_cnt(), 3);
+}
@@ -0,0 +1,74 @@
+use {
+	std::collections::HashSet,
+	wordle::{assist::*, plate::*},
+	LetterState::*,
+};
+
+fn words(list: &[&str]) -> Vec<Word> {
+	list.iter().map(|w| word_from_str(w).unwrap()).collect()
+}
+
+#[test]
+fn test_parse() {
+	let crane = word_from_str("crane").unwrap();
+	assert_eq!(
+		"crane GYXXg".parse(),
+		Ok(AssistInput::Step(
+			crane,
+			[Correct, Occured, Redundant, Redundant, Correct]
+		))
+	);
+	assert_eq!("crane gyrrg".parse::<AssistInput>(), "crane GYXXG".parse());
+	assert_eq!(" Undo ".parse(), Ok(AssistInput::Undo));
+	assert_eq!(
+		"crane GYXXZ".parse::<AssistInput>(),
+		Err(AssistError::Feedback('Z', 4))
+	);
+	assert_eq!(
+		AssistError::Feedback('Z', 4).to_string(),
+		"invalid feedback 'Z' at position 5, use G, Y, or X or R for grey"
+	);
+	assert_eq!(
+		"crane GYXX".parse::<AssistInput>(),
+		Err(AssistError::FeedbackLength(4))
+	);
+	assert!(matches!(
+		"crane".parse::<AssistInput>(),
+		Err(AssistError::Format(_))
+	));
+	assert!(matches!(
+		"cr4ne GGGGG".parse::<AssistInput>(),
+		Err(AssistError::Word(_))
+	));
+	assert_eq!(LetterState::from_char('y'), Some(Occured));
+	assert_eq!(LetterState::from_char('B'), None);
+}
+
+#[test]
+fn test_undo() {
+	let list_final = words(&["build", "guild", "built", "quilt"]);
+	let set_acceptable: HashSet<Word> = words(&["build", "guild", "built", "quilt", "quack"])
+		.into_iter()
+		.collect();
+	let mut assistant = Assistant::new(&list_final, &set_acceptable, None);
+	assert_eq!(assistant.apply(AssistInput::Undo), Err(AssistError::Empty));
+
+	assistant.apply("build XGGGG".parse().unwrap()).unwrap();
+	assert_eq!(assistant.candidates(), &words(&["guild"])[..]);
+	assert_eq!(
+		assistant.suggestions(SUGGESTIONS),
+		vec![(list_final[1], 1.0)]
+	);
+	// contradicts the last feedback
+	assert_eq!(
+		assistant.apply("guild GGGGX".parse().unwrap()),
+		Err(AssistError::NoCandidates)
+	);
+	assert_eq!(assistant.history().len(), 1);
+
+	assistant.apply(AssistInput::Undo).unwrap();
+	assert_eq!(assistant.candidates(), &list_final[..]);
+	assert!(!assistant.is_solved());
+	assistant.apply("quilt ggggg".parse().unwrap()).unwrap();
+	assert!(assistant.is_solved());
+}
@@ -0,0 +1,70 @@
+use {
+	std::collections::HashSet,
+	wordle::{challenge::*, plate::*},
+};
+
+fn word(s: &str) -> Word {
+	word_from_str(s).unwrap()
+}
+
+#[test]
+fn test_round_trip() {
+	for difficulty in [Difficulty::Normal, Difficulty::Hard, Difficulty::Strict] {
+		for max_guesses in [1, 6, 15] {
+			for answer in ["AAAAA", "CRANE", "ZZZZZ"] {
+				let challenge = Challenge::new(word(answer), difficulty, max_guesses).unwrap();
+				let code = challenge.encode();
+				assert_eq!(code.len(), 9);
+				assert!(!code.contains(answer));
+				assert_eq!(Challenge::decode(&code), Ok(challenge));
+				assert_eq!(Challenge::decode(&code.to_lowercase()), Ok(challenge));
+			}
+		}
+	}
+	let challenge = Challenge::new(word("build"), Difficulty::Hard, 2).unwrap();
+	assert_eq!(challenge.encode(), "MEVQ44YY3");
+	assert_eq!(Challenge::decode("mevq-44yy3"), Ok(challenge));
+}
+
+#[test]
+fn test_invalid() {
+	assert_eq!(
+		Challenge::new(word("crane"), Difficulty::Normal, 16),
+		Err(ChallengeError::MaxGuesses(16))
+	);
+	assert_eq!(
+		Challenge::decode("MEVQ44YY"),
+		Err(ChallengeError::Malformed)
+	);
+	assert_eq!(
+		Challenge::decode("MEVQ44YYU"),
+		Err(ChallengeError::Malformed)
+	);
+	// a mistyped digit fails the checksum
+	let mistyped = ["NEVQ44YY3", "MEVQ45YY3", "MEVQ44YY4"];
+	for code in mistyped {
+		assert_eq!(
+			Challenge::decode(code),
+			Err(ChallengeError::Malformed),
+			"{}",
+			code
+		);
+	}
+
+	let challenge = Challenge::new(word("crane"), Difficulty::Normal, 6).unwrap();
+	let set_acceptable: HashSet<Word> = [word("build")].into_iter().collect();
+	assert_eq!(
+		challenge.check(&set_acceptable),
+		Err(ChallengeError::NotAcceptable(word("crane")))
+	);
+}
+
+#[test]
+fn test_max_guesses() {
+	let mut plate = Plate::with_max_guesses(&word("build"), Difficulty::Normal, 2);
+	plate.guess(&word("crane")).unwrap();
+	assert!(!plate.is_over());
+	plate.guess(&word("crane")).unwrap();
+	assert!(plate.is_over() && !plate.is_win());
+	assert_eq!(plate.max_guesses(), 2);
+}
@@ -1,8 +1,10 @@
+#![allow(dead_code)]
+
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::*;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, Command, Output, Stdio};
 
 use assert_json_diff::assert_json_matches;
 use lazy_static::lazy_static;
@@ -16,6 +18,44 @@ use serde_json;
 lazy_static! {
     static ref EXE_PATH: PathBuf = env!("CARGO_BIN_EXE_wordle").into();
     static ref MBT_DIR: PathBuf = "./wordle-mbt".into();
+    static ref EMPTY_CONFIG_HOME: PathBuf = {
+        let dir = std::env::temp_dir().join(format!("wordle-empty-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    };
+}
+
+/// keep the program under test away from the config of the user:
+/// no WORDLE_* variables and XDG_CONFIG_HOME at `config_home`
+pub fn isolate<'a>(command: &'a mut Command, config_home: &Path) -> &'a mut Command {
+    for (var, _) in std::env::vars_os() {
+        if var.to_string_lossy().starts_with("WORDLE_") {
+            command.env_remove(var);
+        }
+    }
+    command.env("XDG_CONFIG_HOME", config_home)
+}
+
+/// the program under test, with an empty XDG config dir
+pub fn wordle() -> Command {
+    let mut command = Command::new(EXE_PATH.as_os_str());
+    isolate(&mut command, &EMPTY_CONFIG_HOME);
+    command
+}
+
+/// run the program with `args` and `envs` in a fresh XDG config dir,
+/// holding `config` as wordle/config.toml, `name` tells the dirs of a test binary apart
+pub fn run_with_config(name: &str, args: &[&str], config: &str, envs: &[(&str, &str)]) -> Output {
+    let dir = std::env::temp_dir().join(format!("wordle-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(dir.join("wordle")).unwrap();
+    std::fs::write(dir.join("wordle").join("config.toml"), config).unwrap();
+    let output = isolate(&mut Command::new(EXE_PATH.as_os_str()), &dir)
+        .args(args)
+        .envs(envs.iter().copied())
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    output
 }
 
 pub struct TestCase {
@@ -82,7 +122,7 @@ impl TestCase {
             Command::new(EXE_PATH.as_os_str())
         };
         // command options for user program
-        let mut command = command
+        let mut command = isolate(&mut command, &EMPTY_CONFIG_HOME)
             .args(&self.arguments)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
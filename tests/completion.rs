@@ -0,0 +1,59 @@
+use {
+	clap_complete::Shell,
+	wordle::{completion::write_completions, config::cli},
+};
+
+mod common;
+
+/// stdout of the binary with `args` and a default config file of `config`
+fn wordle(name: &str, args: &[&str], config: &str) -> String {
+	let output = common::run_with_config(name, args, config, &[]);
+	assert!(
+		output.status.success(),
+		"{}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	return String::from_utf8(output.stdout).unwrap();
+}
+
+#[test]
+fn test_scripts() {
+	for shell in [
+		Shell::Bash,
+		Shell::Zsh,
+		Shell::Fish,
+		Shell::Elvish,
+		Shell::PowerShell,
+	] {
+		let mut buf = vec![];
+		write_completions(shell, &mut cli(), &mut buf).unwrap();
+		let script = String::from_utf8(buf).unwrap();
+		assert!(script.contains("leaderboard"), "{}", shell);
+	}
+	let script = wordle("bash", &["completions", "bash"], "");
+	assert!(script.contains("__complete word"));
+	assert!(script
+		.ends_with("complete -F _wordle_dynamic -o nosort -o bashdefault -o default wordle\n"));
+	let script = wordle("fish", &["completions", "fish"], "");
+	assert!(script.contains("__complete profile"));
+}
+
+#[test]
+fn test_manpage() {
+	let page = wordle("manpage", &["manpage"], "");
+	assert!(page.contains(".TH wordle 1"), "{}", page);
+	assert!(page.contains("completions"));
+	assert!(!page.contains("__complete"));
+}
+
+#[test]
+fn test_dynamic() {
+	let words = wordle("word", &["__complete", "word", "CRAN"], "");
+	assert_eq!(words, "crane\ncrank\ncrans\n");
+	let profiles = wordle(
+		"profile",
+		&["__complete", "profile"],
+		"seed = 1\n\n[profile.kids]\nseed = 2\n\n[profile.hard]\ndifficult = true\n",
+	);
+	assert_eq!(profiles, "hard\nkids\n");
+}
@@ -0,0 +1,97 @@
+use std::{ffi::OsStr, os::unix::ffi::OsStrExt, process::Output};
+
+mod common;
+
+/// run `wordle config show` with a default config file
+fn config_show(name: &str, config: &str, envs: &[(&str, &str)]) -> Output {
+	common::run_with_config(name, &["config", "show"], config, envs)
+}
+
+#[test]
+fn test_env_override() {
+	let output = config_show(
+		"env",
+		"seed = 1\nstats = true\n\n[profile.kids]\nseed = 2\n",
+		&[
+			("WORDLE_PROFILE", "kids"),
+			("WORDLE_SEED", "3"),
+			("WORDLE_PLAYERS", "alice,bob"),
+			("WORDLE_NAME", "42"),
+		],
+	);
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	let lines: Vec<&str> = stdout.lines().collect();
+	assert!(
+		lines.contains(&"seed = 3  # environment variable WORDLE_SEED"),
+		"{}",
+		stdout
+	);
+	assert!(lines.contains(&r#"players = ["alice","bob"]  # environment variable WORDLE_PLAYERS"#));
+	assert!(lines.contains(&r#"name = "42"  # environment variable WORDLE_NAME"#));
+	assert!(lines
+		.iter()
+		.any(|l| l.starts_with("stats = true  # config file ")));
+	assert!(lines.contains(&"# word is not set"));
+}
+
+#[test]
+fn test_invalid_env() {
+	let output = config_show("invalid", "", &[("WORDLE_SEED", "tomorrow")]);
+	assert!(!output.status.success());
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("WORDLE_SEED"), "{}", stderr);
+
+	let output = config_show("profile", "", &[("WORDLE_PROFILE", "hard")]);
+	assert!(!output.status.success());
+}
+
+#[test]
+fn test_explicit_false() {
+	let config = "difficult = true\nstats = true\n\n[profile.easy]\ndifficult = false\n";
+	let output = config_show(
+		"false",
+		config,
+		&[("WORDLE_PROFILE", "easy"), ("WORDLE_STATS", "false")],
+	);
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	let lines: Vec<&str> = stdout.lines().collect();
+	assert!(
+		lines
+			.iter()
+			.any(|l| l.starts_with("difficult = false  # profile easy of ")),
+		"{}",
+		stdout
+	);
+	assert!(lines.contains(&"stats = false  # environment variable WORDLE_STATS"));
+
+	let output = config_show("true", config, &[]);
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout
+		.lines()
+		.any(|l| l.starts_with("difficult = true  # config file ")));
+}
+
+#[test]
+fn test_env_not_unicode() {
+	let invalid = OsStr::from_bytes(b"\xff");
+	let output = common::wordle()
+		.args(["config", "show"])
+		.env("WORDLE_TEST_OTHER", invalid)
+		.env("OTHER", invalid)
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+
+	let output = common::wordle()
+		.args(["config", "show"])
+		.env("WORDLE_SEED", invalid)
+		.output()
+		.unwrap();
+	assert!(!output.status.success());
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(
+		stderr.contains("WORDLE_SEED is not valid unicode"),
+		"{}",
+		stderr
+	);
+}
@@ -0,0 +1,104 @@
+use ntest::timeout;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_09_invalid_letters() {
+	// guesses containing digits or punctuation are rejected
+	TestCase::read("09_01_invalid_letters").run_and_compare_result();
+	// word lists containing digits or punctuation are rejected
+	TestCase::read("09_02_invalid_letter_list").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_10_strict_mode() {
+	// absent letters and known positions are rejected
+	TestCase::read("10_01_strict_mode").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_11_analyze() {
+	// post-mortem of each guess
+	TestCase::read("11_01_analyze").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_12_hot_seat() {
+	// players take turns on a shared answer
+	TestCase::read("12_01_hot_seat").run_and_compare_result();
+	// alternate answers, statistic of each player in one state file
+	TestCase::read("12_02_hot_seat_state").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_13_leaderboard() {
+	// rank users of a state directory, with fastest solves of the daily answer
+	TestCase::read("13_01_leaderboard").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_14_timed() {
+	// every guess is too late for a zero second countdown
+	TestCase::read("14_01_countdown").run_and_compare_result();
+	// nothing to play in a zero minute time attack
+	TestCase::read("14_02_time_attack").run_and_compare_result();
+	// statistic output keeps two lines with timed games in the state
+	TestCase::read("14_03_timed_stats").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_15_achievements() {
+	// unlocked achievements are kept in the state file
+	TestCase::read("15_01_achievements").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_16_archive() {
+	// pick a past day, its game is marked in the state file
+	TestCase::read("16_01_archive").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_17_challenge() {
+	// difficult mode with 2 guesses, answer BUILD
+	TestCase::read("17_01_challenge").run_and_compare_result();
+	TestCase::read("17_02_challenge_create").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_18_config() {
+	// TOML config file with a profile
+	TestCase::read("18_01_config_show").run_and_compare_result();
+	TestCase::read("18_02_config_profile").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_19_subcommands() {
+	// options before `play` are kept
+	TestCase::read("19_01_play_subcommand").run_and_compare_result();
+	TestCase::read("19_02_lists").run_and_compare_result();
+	TestCase::read("19_03_solve").run_and_compare_result();
+	TestCase::read("19_04_stats").run_and_compare_result();
+	// game options only go with playing
+	TestCase::read("19_05_subcommand_conflict").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_20_assist() {
+	// feedback of a puzzle played elsewhere, with undo and a mistyped line
+	TestCase::read("20_01_assist").run_and_compare_result();
+}
@@ -0,0 +1,47 @@
+use wordle::{knowledge::Knowledge, plate::*};
+
+fn word(s: &str) -> Word {
+	word_from_str(s).unwrap()
+}
+
+#[test]
+fn test_knowledge_from_history() {
+	let mut plate = Plate::new(&word("build"), Difficulty::Normal);
+	plate.guess(&word("hello")).unwrap();
+	plate.guess(&word("lucky")).unwrap();
+	let knowledge = Knowledge::from_history(plate.history());
+	assert_eq!(&knowledge, plate.knowledge());
+
+	assert_eq!(knowledge.fixed(1), Some('U'));
+	assert_eq!(knowledge.fixed(3), Some('L'));
+	assert_eq!(knowledge.fixed(0), None);
+	assert_eq!(knowledge.allowed(1).collect::<String>(), "U");
+	assert!(!knowledge.is_allowed(0, 'L'));
+	assert!(!knowledge.is_allowed(0, 'H'));
+	assert!(knowledge.is_allowed(0, 'B'));
+	// the only L is fixed at position 4
+	assert!(!knowledge.is_allowed(4, 'L'));
+
+	assert_eq!(knowledge.min_cnt('L'), 1);
+	assert_eq!(knowledge.max_cnt('L'), Some(1));
+	assert_eq!(knowledge.max_cnt('E'), Some(0));
+	assert_eq!(knowledge.max_cnt('B'), None);
+}
+
+#[test]
+fn test_knowledge_matches() {
+	let mut plate = Plate::new(&word("build"), Difficulty::Normal);
+	plate.guess(&word("lucky")).unwrap();
+	let knowledge = plate.knowledge();
+	assert!(knowledge.matches(&word("build")));
+	assert!(knowledge.matches(&word("guild")));
+	assert!(!knowledge.matches(&word("lulls")));
+	assert!(!knowledge.matches(&word("bulky")));
+	assert_eq!(
+		knowledge.check(&word("audio"), Difficulty::Hard),
+		Err(GuessError::MissingOccured {
+			letter: 'L'
+		})
+	);
+	assert_eq!(knowledge.check(&word("audio"), Difficulty::Normal), Ok(()));
+}
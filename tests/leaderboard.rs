@@ -0,0 +1,74 @@
+use wordle::{
+	leaderboard::{load_dir, Leaderboard},
+	plate::*,
+	statistic::Statistic,
+};
+
+fn statistic(games: &[(&str, &[&str])]) -> Statistic {
+	let games: Vec<serde_json::Value> = games
+		.iter()
+		.map(|(answer, guesses)| serde_json::json!({ "answer": answer, "guesses": guesses }))
+		.collect();
+	Statistic::load_from_json(&serde_json::json!({ "games": games }).to_string()).unwrap()
+}
+
+#[test]
+fn test_streaks() {
+	let s = statistic(&[
+		("CRANE", &["CRANE"]),
+		("SLATE", &["SLATE"]),
+		("TRACE", &["CRANE"]),
+		("PLANT", &["PLANT"]),
+	]);
+	assert_eq!((s.current_streak(), s.max_streak()), (1, 2));
+	assert_eq!(
+		(
+			Statistic::new().current_streak(),
+			Statistic::new().max_streak()
+		),
+		(0, 0)
+	);
+}
+
+#[test]
+fn test_leaderboard_daily() {
+	let users = vec![
+		(
+			"alice".to_string(),
+			statistic(&[("CRANE", &["SLATE", "CRANE"])]),
+		),
+		// only the first attempt of the daily answer counts
+		(
+			"bob".to_string(),
+			statistic(&[("CRANE", &["SLATE"]), ("CRANE", &["CRANE"])]),
+		),
+		("carol".to_string(), statistic(&[("CRANE", &["CRANE"])])),
+	];
+	let daily = word_from_str("crane").unwrap();
+	let leaderboard = Leaderboard::new(&users, Some(&daily));
+
+	let ranked: Vec<&str> = leaderboard
+		.entries
+		.iter()
+		.map(|e| e.user.as_str())
+		.collect();
+	assert_eq!(ranked, vec!["carol", "alice", "bob"]);
+	let solves: Vec<(&str, usize)> = leaderboard
+		.solves
+		.iter()
+		.map(|s| (s.user.as_str(), s.guesses))
+		.collect();
+	assert_eq!(solves, vec![("carol", 1), ("alice", 2)]);
+
+	let json = leaderboard.store_to_json();
+	assert_eq!(Leaderboard::load_from_json(&json).unwrap(), leaderboard);
+	assert!(Leaderboard::new(&users, None).solves.is_empty());
+}
+
+#[test]
+fn test_load_dir() {
+	let users = load_dir(std::path::Path::new("tests/data/13_01_leaderboard")).unwrap();
+	let names: Vec<&str> = users.iter().map(|(user, _)| user.as_str()).collect();
+	assert_eq!(names, vec!["alice", "bob", "carol"]);
+	assert_eq!(users[0].1.success_cnt(), 3);
+}
@@ -0,0 +1,109 @@
+use {
+	std::collections::HashSet,
+	wordle::{
+		analysis::ranked_guesses,
+		pattern::{matrix::*, *},
+		plate::*,
+	},
+};
+
+fn words(list: &[&str]) -> Vec<Word> {
+	list.iter().map(|w| word_from_str(w).unwrap()).collect()
+}
+
+#[test]
+fn test_build() {
+	let answers = words(&["build", "guild", "quilt"]);
+	let set_acceptable: HashSet<Word> = words(&["quilt", "build", "guild", "crane"])
+		.into_iter()
+		.collect();
+	let matrix = PatternMatrix::build(&set_acceptable, &answers);
+	assert_eq!(
+		matrix.guesses(),
+		&words(&["build", "crane", "guild", "quilt"])[..]
+	);
+	assert!(!matrix.is_mapped());
+	for (i, guess) in matrix.guesses().iter().enumerate() {
+		assert_eq!(matrix.guess_index(guess), Some(i));
+		for (j, answer) in answers.iter().enumerate() {
+			assert_eq!(matrix.get(i, j), Some(evaluate_pattern(guess, answer)));
+		}
+	}
+	let build = matrix.guess_index(&answers[0]).unwrap();
+	let counts = matrix.counts(build, &[0, 1, 2]);
+	assert_eq!(counts[Pattern::WIN.index()], 1);
+	assert_eq!(matrix.answer_index(&answers[2]), Some(2));
+	assert_eq!(matrix.answer_index(&words(&["crane"])[0]), None);
+}
+
+#[test]
+fn test_ranked_guesses() {
+	let answers = words(&["build", "guild", "built", "quilt"]);
+	let set_acceptable: HashSet<Word> = words(&["build", "guild", "built", "quilt", "crane"])
+		.into_iter()
+		.collect();
+	let matrix = PatternMatrix::build(&set_acceptable, &answers);
+	let candidates = words(&["guild", "built", "quilt"]);
+	assert_eq!(
+		ranked_guesses(&candidates, &set_acceptable, Some(&matrix), 5),
+		ranked_guesses(&candidates, &set_acceptable, None, 5)
+	);
+
+	// computed for words the matrix does not have
+	let candidates = words(&["build", "crane"]);
+	assert!(matrix
+		.expected_remaining(&set_acceptable, &candidates)
+		.is_none());
+	assert_eq!(
+		ranked_guesses(&candidates, &set_acceptable, Some(&matrix), 5),
+		ranked_guesses(&candidates, &set_acceptable, None, 5)
+	);
+}
+
+#[test]
+fn test_cache() {
+	let dir = std::env::temp_dir().join(format!("wordle-matrix-{}", std::process::id()));
+	let path = dir.join("patterns.bin");
+	let answers = words(&["build", "guild"]);
+	let set_acceptable: HashSet<Word> = words(&["build", "guild", "crane"]).into_iter().collect();
+
+	assert!(PatternMatrix::open(&path, &set_acceptable, &answers)
+		.unwrap()
+		.is_none());
+	let built = PatternMatrix::load_or_build(&path, &set_acceptable, &answers).unwrap();
+	assert!(!built.is_mapped());
+	let mapped = PatternMatrix::load_or_build(&path, &set_acceptable, &answers).unwrap();
+	assert!(mapped.is_mapped());
+	for i in 0..3 {
+		assert_eq!(mapped.row(i), built.row(i));
+	}
+
+	// stale once a list changes
+	let answers = words(&["guild", "build"]);
+	assert!(PatternMatrix::open(&path, &set_acceptable, &answers)
+		.unwrap()
+		.is_none());
+	let rebuilt = PatternMatrix::load_or_build(&path, &set_acceptable, &answers).unwrap();
+	assert!(!rebuilt.is_mapped());
+	assert!(PatternMatrix::open(&path, &set_acceptable, &answers)
+		.unwrap()
+		.is_some());
+
+	// a damaged pattern is only found when read
+	let mut bytes = std::fs::read(&path).unwrap();
+	*bytes.last_mut().unwrap() = 243;
+	std::fs::write(&path, &bytes).unwrap();
+	let damaged = PatternMatrix::open(&path, &set_acceptable, &answers)
+		.unwrap()
+		.unwrap();
+	assert_eq!(damaged.get(2, 1), None);
+	assert_eq!(damaged.counts(2, &[0, 1]).iter().sum::<u32>(), 1);
+
+	// truncated
+	bytes.pop();
+	std::fs::write(&path, &bytes).unwrap();
+	assert!(PatternMatrix::open(&path, &set_acceptable, &answers)
+		.unwrap()
+		.is_none());
+	std::fs::remove_dir_all(&dir).unwrap();
+}
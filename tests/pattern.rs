@@ -0,0 +1,42 @@
+use wordle::{pattern::*, plate::*};
+
+fn word(s: &str) -> Word {
+	word_from_str(s).unwrap()
+}
+
+#[test]
+fn test_encoding() {
+	for value in 0..PATTERNS as u8 {
+		let pattern = Pattern::from_u8(value).unwrap();
+		assert_eq!(Pattern::new(&pattern.state()), pattern);
+		assert_eq!(pattern.as_u8(), value);
+	}
+	assert_eq!(Pattern::from_u8(243), None);
+	assert_eq!(Pattern::from_u8(0).unwrap().to_string(), "RRRRR");
+	assert_eq!(Pattern::WIN.to_string(), "GGGGG");
+	// first letter is the most significant digit
+	let pattern = evaluate_pattern(&word("crane"), &word("cabin"));
+	assert_eq!(pattern.to_string(), "GRYYR");
+	assert_eq!(pattern.as_u8(), 2 * 81 + 9 + 3);
+}
+
+#[test]
+fn test_same_as_game() {
+	let answers: Vec<Word> = ["speed", "abbey", "eerie", "crane", "llama"]
+		.iter()
+		.map(|w| word(w))
+		.collect();
+	let mut out = [Pattern::default(); 5];
+	for guess in &answers {
+		evaluate_into(guess, &answers, &mut out);
+		for (answer, pattern) in answers.iter().zip(out) {
+			let mut plate = Plate::new(answer, Difficulty::Normal);
+			plate.guess(guess).unwrap();
+			assert_eq!(pattern.state(), plate.history()[0].1);
+			assert_eq!(pattern.is_win(), plate.is_win());
+		}
+		let counts = pattern_counts(guess, &answers);
+		assert_eq!(counts.iter().sum::<u32>(), 5);
+		assert_eq!(counts[Pattern::WIN.index()], 1);
+	}
+}
@@ -0,0 +1,116 @@
+use {std::time::Duration, wordle::plate::*};
+
+fn word(s: &str) -> Word {
+	word_from_str(s).unwrap()
+}
+
+#[test]
+fn test_guess_error_difficult() {
+	let mut plate = Plate::new(&word("build"), Difficulty::Hard);
+	plate.guess(&word("built")).unwrap();
+	assert_eq!(
+		plate.guess(&word("guilt")),
+		Err(GuessError::MissingCorrect {
+			letter:   'B',
+			position: 0,
+		})
+	);
+
+	let mut plate = Plate::new(&word("build"), Difficulty::Hard);
+	plate.guess(&word("lucky")).unwrap();
+	assert_eq!(
+		plate.guess(&word("audio")),
+		Err(GuessError::MissingOccured {
+			letter: 'L'
+		})
+	);
+	assert_eq!(plate.count(), 1);
+}
+
+#[test]
+fn test_guess_error_strict() {
+	// absent letters
+	let mut plate = Plate::new(&word("build"), Difficulty::Strict);
+	plate.guess(&word("crane")).unwrap();
+	assert_eq!(
+		plate.guess(&word("cloud")),
+		Err(GuessError::Absent {
+			letter: 'C'
+		})
+	);
+	plate.guess(&word("tulip")).unwrap();
+
+	// Occured letters cannot stay at the same position
+	let mut plate = Plate::new(&word("build"), Difficulty::Strict);
+	plate.guess(&word("lucky")).unwrap();
+	assert_eq!(
+		plate.guess(&word("lulls")),
+		Err(GuessError::Excluded {
+			letter:   'L',
+			position: 0,
+		})
+	);
+	plate.guess(&word("build")).unwrap();
+	assert!(plate.is_win());
+
+	// a Redundant duplicate caps the letter count
+	let mut plate = Plate::new(&word("build"), Difficulty::Strict);
+	plate.guess(&word("hello")).unwrap();
+	assert_eq!(
+		plate.guess(&word("skull")),
+		Err(GuessError::TooMany {
+			letter: 'L',
+			max:    1,
+		})
+	);
+
+	// the same guesses are fine in normal difficult mode
+	let mut plate = Plate::new(&word("build"), Difficulty::Hard);
+	plate.guess(&word("hello")).unwrap();
+	plate.guess(&word("skull")).unwrap();
+}
+
+#[test]
+fn test_guess_error_game_over() {
+	let mut plate = Plate::new(&word("build"), Difficulty::Normal);
+	plate.guess(&word("build")).unwrap();
+	assert!(plate.is_over());
+	assert_eq!(plate.guess(&word("crane")), Err(GuessError::GameOver));
+
+	let mut plate = Plate::new(&word("build"), Difficulty::Normal);
+	for _ in 0..MAX_GUESSES {
+		plate.guess(&word("crane")).unwrap();
+	}
+	assert!(plate.is_over() && !plate.is_win());
+	assert_eq!(plate.guess(&word("build")), Err(GuessError::GameOver));
+}
+
+#[test]
+fn test_word_error() {
+	assert_eq!(word_from_str("buil"), Err(WordError::Length(4)));
+	assert_eq!(word_from_str("abc1d"), Err(WordError::Letter('1', 3)));
+	assert_eq!(word("Build").to_string(), "BUILD");
+}
+
+#[test]
+fn test_guess_timed() {
+	let mut plate = Plate::new(&word("build"), Difficulty::Hard);
+	plate
+		.guess_timed(&word("crane"), Duration::from_millis(1500))
+		.unwrap();
+	plate
+		.guess_timed(&word("built"), Duration::from_millis(500))
+		.unwrap();
+	assert_eq!(plate.durations().len(), 2);
+	assert_eq!(plate.duration(), Duration::from_secs(2));
+
+	// rejected guesses take no time
+	assert!(plate
+		.guess_timed(&word("guilt"), Duration::from_secs(1))
+		.is_err());
+	assert_eq!(plate.durations().len(), 2);
+
+	plate.time_up();
+	assert!(plate.is_time_up() && plate.is_over() && !plate.is_win());
+	assert!(plate.guess(&word("build")).is_err());
+}
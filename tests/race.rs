@@ -0,0 +1,201 @@
+use {
+	std::{
+		io::{BufRead, BufReader, Write},
+		process::{Child, Command, Stdio},
+		thread,
+		time::Duration,
+	},
+	wordle::{
+		config::load_lists,
+		plate::*,
+		race::{net::RaceClient, *},
+		word_gen::rand_words,
+	},
+};
+
+mod common;
+
+fn names(players: &[&Racer]) -> Vec<String> {
+	players.iter().map(|r| r.name.clone()).collect()
+}
+
+#[test]
+fn test_room_join() {
+	let mut room = Room::new(7, 3, 2);
+	assert_eq!(
+		room.join("alice"),
+		Ok(vec![ServerMsg::Waiting {
+			players: vec!["alice".to_string()],
+			needed:  2,
+		}])
+	);
+	assert!(room.join("alice").is_err());
+	assert!(room.guess("alice", "GGGGG").is_err());
+
+	let msgs = room.join("bob").unwrap();
+	assert_eq!(
+		msgs[1],
+		ServerMsg::Start {
+			seed: 7, day: 3
+		}
+	);
+	assert!(matches!(&msgs[2], ServerMsg::Board { players } if players.len() == 2));
+	assert!(room.join("carol").is_err());
+}
+
+#[test]
+fn test_room_race() {
+	let mut room = Room::new(0, 1, 2);
+	room.join("alice").unwrap();
+	room.join("bob").unwrap();
+
+	assert!(room.guess("alice", "GGXGG").is_err());
+	assert!(room.guess("carol", "GGGGG").is_err());
+	room.guess("alice", "GYRRR").unwrap();
+	room.guess("bob", "GGGGG").unwrap();
+	assert!(room.guess("bob", "GGGGG").is_err());
+	let msgs = room.guess("alice", "GGGGG").unwrap();
+
+	let players = match &msgs[0] {
+		ServerMsg::Board {
+			players,
+		} => players.clone(),
+		msg => panic!("unexpected {:?}", msg),
+	};
+	assert_eq!(players[0].states, vec!["GYRRR", "GGGGG"]);
+	assert_eq!(players[1].finish, Some(1));
+	assert!(players.iter().all(Racer::is_over));
+	assert_eq!(names(&ranking(&players)), vec!["bob", "alice"]);
+}
+
+#[test]
+fn test_room_leave() {
+	let mut room = Room::new(0, 1, 2);
+	room.join("alice").unwrap();
+	room.leave("alice");
+	assert!(room.is_empty());
+
+	room.join("alice").unwrap();
+	room.join("bob").unwrap();
+	room.guess("alice", "RRRRR").unwrap();
+	let msgs = room.leave("alice");
+	assert!(matches!(&msgs[0], ServerMsg::Board { players } if players[0].left));
+	assert!(room.guess("alice", "GGGGG").is_err());
+	room.guess("bob", "GGGGG").unwrap();
+	assert!(room.leave("bob").is_empty());
+}
+
+fn spawn_server() -> (Child, String) {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_server"))
+		.args(["--listen", "127.0.0.1:0", "--race", "127.0.0.1:0"])
+		.stdout(Stdio::piped())
+		.spawn()
+		.unwrap();
+	let mut stdout = BufReader::new(child.stdout.take().unwrap());
+	let mut line = String::new();
+	stdout.read_line(&mut line).unwrap();
+	line.clear();
+	stdout.read_line(&mut line).unwrap();
+	let addr = line.trim().trim_start_matches("racing on ").to_string();
+	return (child, addr);
+}
+
+#[test]
+#[ntest::timeout(10000)]
+fn test_race_loopback() {
+	let (mut child, addr) = spawn_server();
+	let url = format!("{}/race/lunch?seed=7&day=3", addr);
+
+	let alice = {
+		let url = url.clone();
+		thread::spawn(move || RaceClient::join(&url, "alice", |_| {}).unwrap())
+	};
+	thread::sleep(Duration::from_millis(100));
+	let bob = RaceClient::join(&url, "bob", |_| {}).unwrap();
+	let alice = alice.join().unwrap();
+	assert_eq!((alice.seed(), alice.day()), (7, 3));
+	assert_eq!((bob.seed(), bob.day()), (7, 3));
+
+	alice.send_guess(&[LetterState::Correct; 5]).unwrap();
+	bob.send_guess(&[LetterState::Redundant; 5]).unwrap();
+	while !bob.board().first().is_some_and(Racer::is_win) {
+		thread::sleep(Duration::from_millis(20));
+	}
+	drop(bob);
+	while !alice.is_finished() {
+		thread::sleep(Duration::from_millis(20));
+	}
+	let board = alice.board();
+	assert_eq!(board[0].states, vec!["GGGGG"]);
+	assert!(board[1].left);
+
+	child.kill().unwrap();
+	child.wait().unwrap();
+}
+
+#[test]
+#[ntest::timeout(10000)]
+fn test_race_room_not_joined() {
+	let (mut child, addr) = spawn_server();
+	// a connection that never joins leaves no room behind
+	let (mut ws, _) = tungstenite::connect(format!("{}/race/quiz?seed=5&players=1", addr)).unwrap();
+	ws.send(tungstenite::Message::Text(
+		r#"{"type":"guess","state":"GGGGG"}"#.to_string(),
+	))
+	.unwrap();
+	assert!(ws
+		.read()
+		.unwrap()
+		.to_string()
+		.contains("join the race first"));
+	drop(ws);
+	thread::sleep(Duration::from_millis(200));
+
+	let url = format!("{}/race/quiz?seed=7&day=2&players=1", addr);
+	let racer = RaceClient::join(&url, "alice", |_| {}).unwrap();
+	assert_eq!((racer.seed(), racer.day()), (7, 2));
+
+	child.kill().unwrap();
+	child.wait().unwrap();
+}
+
+fn spawn_racer(url: &str, name: &str, input: String) -> Child {
+	let mut child = common::wordle()
+		.args(["--race", url, "--name", name])
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()
+		.unwrap();
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(input.as_bytes())
+		.unwrap();
+	return child;
+}
+
+#[test]
+#[ntest::timeout(20000)]
+fn test_race_cli() {
+	let (mut child, addr) = spawn_server();
+	let url = format!("{}/race/cli?seed=1&day=2", addr);
+	let (_, list_final) = load_lists(None, None).unwrap();
+	let answer = word_to_str(&rand_words(&list_final, 1, 2)().unwrap());
+	let other = match answer.as_str() {
+		"CRANE" => "SLATE",
+		_ => "CRANE",
+	};
+
+	let x = spawn_racer(&url, "x", format!("{}\n", answer));
+	let y = spawn_racer(&url, "y", format!("{}\n{}\n", other, answer));
+	let x = String::from_utf8(x.wait_with_output().unwrap().stdout).unwrap();
+	let y = String::from_utf8(y.wait_with_output().unwrap().stdout).unwrap();
+
+	let standings = "1 x CORRECT 1\n2 y CORRECT 2\n";
+	assert!(x.ends_with(&format!("CORRECT 1\n{}", standings)), "{}", x);
+	assert!(y.ends_with(&format!("CORRECT 2\n{}", standings)), "{}", y);
+
+	child.kill().unwrap();
+	child.wait().unwrap();
+}
@@ -0,0 +1,258 @@
+use {
+	serde_json::{json, Value},
+	std::{
+		io::{BufRead, BufReader, Read, Write},
+		net::TcpStream,
+		process::{Command, Stdio},
+		time::Instant,
+	},
+	wordle::{config::load_lists, server::*},
+};
+
+fn new_server(state_dir: Option<std::path::PathBuf>) -> GameServer {
+	let (set_acceptable, list_final) = load_lists(None, None).unwrap();
+	GameServer::new(set_acceptable, list_final, state_dir).unwrap()
+}
+
+fn call(server: &mut GameServer, method: &str, url: &str, body: Value) -> (u16, Value) {
+	let response = server.handle(method, url, &body.to_string());
+	(
+		response.status,
+		serde_json::from_str(&response.body).unwrap(),
+	)
+}
+
+#[test]
+fn test_server_game() {
+	let mut server = new_server(None);
+	let (status, game) = call(
+		&mut server,
+		"POST",
+		"/games",
+		json!({ "user": "alice", "word": "build" }),
+	);
+	assert_eq!(status, 201);
+	assert_eq!(game["answer"], Value::Null);
+	let id = game["id"].as_u64().unwrap();
+
+	let url = format!("/games/{}/guesses", id);
+	let (status, err) = call(&mut server, "POST", &url, json!({ "word": "abcde" }));
+	assert_eq!(
+		(status, err["error"].as_str()),
+		(422, Some("not_acceptable"))
+	);
+	let (status, game) = call(&mut server, "POST", &url, json!({ "word": "lucky" }));
+	assert_eq!(status, 200);
+	assert_eq!(game["guesses"][0]["state"], "YGRRR");
+	let (status, game) = call(&mut server, "POST", &url, json!({ "word": "build" }));
+	assert_eq!(status, 200);
+	assert_eq!(
+		(game["win"].as_bool(), game["over"].as_bool()),
+		(Some(true), Some(true))
+	);
+	assert_eq!(game["answer"], "BUILD");
+	let (status, err) = call(&mut server, "POST", &url, json!({ "word": "crane" }));
+	assert_eq!((status, err["error"].as_str()), (422, Some("game_over")));
+
+	let (status, game) = call(&mut server, "GET", &format!("/games/{}", id), json!(null));
+	assert_eq!(
+		(status, game["guesses"].as_array().unwrap().len()),
+		(200, 2)
+	);
+	let (status, stats) = call(&mut server, "GET", "/users/alice/stats", json!(null));
+	assert_eq!((status, stats["success"].as_u64()), (200, Some(1)));
+	assert_eq!(stats["top_words"][0], json!(["BUILD", 1]));
+}
+
+#[test]
+fn test_server_options() {
+	let mut server = new_server(None);
+	let (status, game) = call(
+		&mut server,
+		"POST",
+		"/games",
+		json!({ "difficult": true, "seed": 1, "day": 2 }),
+	);
+	assert_eq!((status, game["user"].as_str()), (201, Some("anonymous")));
+	let (status, err) = call(
+		&mut server,
+		"POST",
+		"/games",
+		json!({ "word": "build", "seed": 1 }),
+	);
+	assert_eq!((status, err["error"].as_str()), (400, Some("bad_request")));
+	let (status, _) = call(&mut server, "POST", "/games", json!({ "user": "../etc" }));
+	assert_eq!(status, 400);
+	let (status, _) = call(&mut server, "GET", "/games/42", json!(null));
+	assert_eq!(status, 404);
+}
+
+#[test]
+fn test_server_day_out_of_range() {
+	let mut server = new_server(None);
+	for day in [0, 2316, u32::MAX] {
+		let (status, err) = call(
+			&mut server,
+			"POST",
+			"/games",
+			json!({ "seed": 1, "day": day }),
+		);
+		assert_eq!((status, err["error"].as_str()), (400, Some("bad_request")));
+	}
+	// still serving
+	let (status, _) = call(&mut server, "POST", "/games", json!({ "day": 2315 }));
+	assert_eq!(status, 201);
+}
+
+#[test]
+fn test_server_state_dir() {
+	let dir = std::env::temp_dir().join(format!("wordle-server-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let mut server = new_server(Some(dir.clone()));
+	let (_, game) = call(
+		&mut server,
+		"POST",
+		"/games",
+		json!({ "user": "bob", "word": "build" }),
+	);
+	let url = format!("/games/{}/guesses", game["id"]);
+	call(&mut server, "POST", &url, json!({ "word": "build" }));
+
+	// a new server picks up the state file
+	let mut server = new_server(Some(dir.clone()));
+	let (_, stats) = call(&mut server, "GET", "/users/bob/stats", json!(null));
+	assert_eq!(stats["success"].as_u64(), Some(1));
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_server_persist_games() {
+	let dir = std::env::temp_dir().join(format!("wordle-server-games-{}", std::process::id()));
+	let mut server = new_server(Some(dir.clone()));
+	let (_, game) = call(
+		&mut server,
+		"POST",
+		"/games",
+		json!({ "user": "carol", "word": "build", "difficult": true }),
+	);
+	let id = game["id"].as_u64().unwrap();
+	let url = format!("/games/{}/guesses", id);
+	call(&mut server, "POST", &url, json!({ "word": "guild" }));
+
+	// a new server reloads the game in progress, rules included
+	let mut server = new_server(Some(dir.clone()));
+	let (status, game) = call(&mut server, "GET", &format!("/games/{}", id), json!(null));
+	assert_eq!(
+		(status, game["guesses"][0]["word"].as_str()),
+		(200, Some("GUILD"))
+	);
+	let (status, err) = call(&mut server, "POST", &url, json!({ "word": "crane" }));
+	assert_eq!(
+		(status, err["error"].as_str()),
+		(422, Some("difficult_mode"))
+	);
+	let (_, game) = call(&mut server, "POST", "/games", json!({ "word": "build" }));
+	assert_eq!(game["id"].as_u64(), Some(id + 1));
+
+	// idle games are dropped from memory but not from the state directory
+	server.prune(Instant::now() + IDLE_TTL);
+	let (status, game) = call(&mut server, "POST", &url, json!({ "word": "build" }));
+	assert_eq!((status, game["win"].as_bool()), (200, Some(true)));
+
+	// finished games are dropped with their files
+	server.prune(Instant::now() + FINISHED_TTL);
+	let (status, _) = call(&mut server, "GET", &format!("/games/{}", id), json!(null));
+	assert_eq!(status, 404);
+	assert!(!dir.join("games").join(format!("{}.json", id)).exists());
+	let (_, stats) = call(&mut server, "GET", "/users/carol/stats", json!(null));
+	assert_eq!(stats["success"].as_u64(), Some(1));
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_server_prune_in_memory() {
+	let mut server = new_server(None);
+	let (_, game) = call(&mut server, "POST", "/games", json!({ "word": "build" }));
+	let url = format!("/games/{}", game["id"]);
+	server.prune(Instant::now() + FINISHED_TTL);
+	assert_eq!(call(&mut server, "GET", &url, json!(null)).0, 200);
+	server.prune(Instant::now() + IDLE_TTL);
+	assert_eq!(call(&mut server, "GET", &url, json!(null)).0, 404);
+}
+
+#[test]
+fn test_server_store_failure() {
+	let dir = std::env::temp_dir().join(format!("wordle-server-fail-{}", std::process::id()));
+	let mut server = new_server(Some(dir.clone()));
+	call(&mut server, "GET", "/users/dave/stats", json!(null));
+	let (_, game) = call(
+		&mut server,
+		"POST",
+		"/games",
+		json!({ "user": "dave", "word": "build" }),
+	);
+	let url = format!("/games/{}/guesses", game["id"]);
+
+	// the state file cannot be written, the winning guess is not taken
+	std::fs::create_dir_all(dir.join("dave.json")).unwrap();
+	let (status, _) = call(&mut server, "POST", &url, json!({ "word": "build" }));
+	assert_eq!(status, 500);
+	let (_, stats) = call(&mut server, "GET", "/users/dave/stats", json!(null));
+	assert_eq!(stats["success"].as_u64(), Some(0));
+
+	std::fs::remove_dir(dir.join("dave.json")).unwrap();
+	let (status, game) = call(&mut server, "POST", &url, json!({ "word": "build" }));
+	assert_eq!(
+		(status, game["guesses"].as_array().unwrap().len()),
+		(200, 1)
+	);
+	let (_, stats) = call(&mut server, "GET", "/users/dave/stats", json!(null));
+	assert_eq!(stats["success"].as_u64(), Some(1));
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+fn http(addr: &str, method: &str, path: &str, body: &str) -> (u16, Value) {
+	let mut stream = TcpStream::connect(addr).unwrap();
+	write!(
+		stream,
+		"{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+		method,
+		path,
+		addr,
+		body.len(),
+		body
+	)
+	.unwrap();
+	let mut response = String::new();
+	stream.read_to_string(&mut response).unwrap();
+	let status = response[9..12].parse().unwrap();
+	let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+	(status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+#[ntest::timeout(10000)]
+fn test_server_loopback() {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_server"))
+		.args(["--listen", "127.0.0.1:0"])
+		.stdout(Stdio::piped())
+		.spawn()
+		.unwrap();
+	let mut line = String::new();
+	BufReader::new(child.stdout.take().unwrap())
+		.read_line(&mut line)
+		.unwrap();
+	let addr = line
+		.trim()
+		.trim_start_matches("listening on http://")
+		.to_string();
+
+	let (status, game) = http(&addr, "POST", "/games", r#"{"word":"crane"}"#);
+	assert_eq!(status, 201);
+	let path = format!("/games/{}/guesses", game["id"]);
+	let (status, game) = http(&addr, "POST", &path, r#"{"word":"crane"}"#);
+	assert_eq!((status, game["win"].as_bool()), (200, Some(true)));
+
+	child.kill().unwrap();
+	child.wait().unwrap();
+}
@@ -0,0 +1,102 @@
+use {
+	std::{io::Write, process::Stdio, time::Duration},
+	wordle::statistic::Statistic,
+};
+
+mod common;
+
+#[test]
+fn test_timing() {
+	let statistic = Statistic::load_from_json(
+		r#"{"games":[
+			{"answer":"BUILD","guesses":["CRANE","BUILD"],"durations":[3000,1000]},
+			{"answer":"CRANE","guesses":["BUILD"]},
+			{"answer":"SLATE","guesses":["SLATE"],"durations":[6000]},
+			{"answer":"PIOUS","guesses":["CRANE"],"durations":[2000]}
+		]}"#,
+	)
+	.unwrap();
+	assert_eq!(statistic.average_time(), Some(Duration::from_secs(4)));
+	assert_eq!(statistic.fastest_time(), Some(Duration::from_secs(4)));
+	assert_eq!(statistic.average_guess_time(), Some(Duration::from_secs(3)));
+
+	// durations are kept in the state file
+	let json = statistic.store_to_json();
+	assert!(json.contains(r#""durations":[3000,1000]"#));
+	assert!(!json.contains(r#""guesses":["BUILD"],"durations""#));
+
+	assert_eq!(Statistic::new().average_time(), None);
+}
+
+#[test]
+fn test_merge() {
+	let mut statistic = Statistic::load_from_json(
+		r#"{"games":[
+			{"answer":"BUILD","guesses":["CRANE","BUILD"]},
+			{"answer":"CRANE","guesses":["BUILD"]}
+		]}"#,
+	)
+	.unwrap();
+	let other = Statistic::load_from_json(
+		r#"{"games":[
+			{"answer":"CRANE","guesses":["BUILD"]},
+			{"answer":"CRANE","guesses":["BUILD"],"archive":3},
+			{"answer":"SLATE","guesses":["CRANE","SLATE"]}
+		],"achievements":["first_guess"]}"#,
+	)
+	.unwrap();
+	assert_eq!(statistic.merge(other), 2);
+	assert_eq!(statistic.games().len(), 4);
+	assert_eq!(statistic.success_cnt(), 2);
+	assert_eq!(statistic.fail_cnt(), 2);
+	assert_eq!(statistic.top5_words().next().unwrap().str, "BUILD");
+	assert_eq!(statistic.achievements().len(), 1);
+
+	// merging again adds nothing
+	let same = Statistic::load_from_json(&statistic.store_to_json()).unwrap();
+	assert_eq!(statistic.merge(same), 0);
+	assert_eq!(statistic.games().len(), 4);
+}
+
+#[test]
+fn test_merge_repeated() {
+	// the same loss twice, e.g. a `--state` file imported into an empty browser
+	let loss = r#"{"answer":"CRANE","guesses":["BUILD","GUILD","QUILT","SLATE","AUDIO","LUCKY"]}"#;
+	let other = format!(r#"{{"games":[{0},{0},{0}]}}"#, loss);
+	let mut statistic = Statistic::new();
+	assert_eq!(
+		statistic.merge(Statistic::load_from_json(&other).unwrap()),
+		3
+	);
+	assert_eq!(statistic.fail_cnt(), 3);
+
+	// only as many as are already here are skipped
+	let mut statistic = Statistic::load_from_json(&format!(r#"{{"games":[{}]}}"#, loss)).unwrap();
+	assert_eq!(
+		statistic.merge(Statistic::load_from_json(&other).unwrap()),
+		2
+	);
+	assert_eq!(statistic.games().len(), 3);
+}
+
+#[test]
+fn test_hot_seat_file() {
+	let json = std::fs::read_to_string("tests/cases/12_02_hot_seat_state.after.json").unwrap();
+	let e = Statistic::load_from_json(&json).err().unwrap();
+	assert!(e.to_string().contains("--players"), "{}", e);
+
+	// a single player game fails before the state file is written
+	let path = std::env::temp_dir().join(format!("wordle-hot-seat-{}.json", std::process::id()));
+	std::fs::write(&path, &json).unwrap();
+	let mut child = common::wordle()
+		.args(["-w", "crane", "--state", path.to_str().unwrap()])
+		.stdin(Stdio::piped())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn()
+		.unwrap();
+	child.stdin.take().unwrap().write_all(b"CRANE\n").unwrap();
+	assert!(!child.wait().unwrap().success());
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), json);
+	std::fs::remove_file(&path).unwrap();
+}